@@ -73,6 +73,13 @@ impl<'a> ApplicationHandler<()> for TextRenderingExample<'a> {
             WindowEvent::CloseRequested => {
                 event_loop.exit();
             }
+            WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+                if let Some(engine) = &mut self.engine {
+                    if engine.set_dpi_scale_factor(scale_factor as f32).is_err() {
+                        println!("Failed to rebuild font atlases");
+                    }
+                }
+            }
             WindowEvent::RedrawRequested => {
                 if let Some(engine) = &mut self.engine {
                     engine.clear_render_queue();
@@ -5,7 +5,7 @@ use plutonium_engine::{
 };
 use std::sync::Arc;
 use wgpu::Surface;
-use winit::event::{ElementState, MouseButton, WindowEvent};
+use winit::event::{ElementState, Ime, MouseButton, WindowEvent};
 use winit::{
     application::ApplicationHandler,
     event::KeyEvent,
@@ -48,6 +48,7 @@ impl<'a> ApplicationHandler<()> for TextureSvgExample<'a> {
             Window::default_attributes().with_title("Moveable Texture SVG Example");
         if let Ok(window) = event_loop.create_window(window_attributes) {
             let window_arc = Arc::new(window);
+            window_arc.set_ime_allowed(true);
             let surface = instance.create_surface(window_arc.clone()).unwrap();
             let size = window_arc.inner_size(); // Get window size
             let scale_factor = window_arc.scale_factor() as f32; // Get DPI scaling factor
@@ -87,6 +88,9 @@ impl<'a> ApplicationHandler<()> for TextureSvgExample<'a> {
             WindowEvent::CursorMoved { position, .. } => {
                 self.mouse_info.mouse_pos.x = position.x as f32;
                 self.mouse_info.mouse_pos.y = position.y as f32;
+                if let Some(window) = &self.window {
+                    window.request_redraw();
+                }
             }
             WindowEvent::MouseInput { state, button, .. } => {
                 if button == MouseButton::Left && state == ElementState::Pressed {
@@ -110,6 +114,19 @@ impl<'a> ApplicationHandler<()> for TextureSvgExample<'a> {
                     self.window.as_ref().unwrap().request_redraw();
                 }
             }
+            WindowEvent::Ime(ime) => {
+                if let Some(text_input) = &self.text_input {
+                    match ime {
+                        Ime::Preedit(preedit, _) => text_input.set_composing(Some(&preedit)),
+                        Ime::Commit(committed) => text_input.commit_composition(&committed),
+                        Ime::Disabled => text_input.set_composing(None),
+                        Ime::Enabled => (),
+                    }
+                }
+                if let Some(window) = &self.window {
+                    window.request_redraw();
+                }
+            }
             WindowEvent::RedrawRequested => {
                 if let Some(engine) = &mut self.engine {
                     engine.clear_render_queue();
@@ -118,6 +135,15 @@ impl<'a> ApplicationHandler<()> for TextureSvgExample<'a> {
                         text_input.render(engine);
                     }
                     engine.render().unwrap();
+                    if let Some(window) = &self.window {
+                        match engine.cursor_icon() {
+                            Some(icon) => {
+                                window.set_cursor_visible(true);
+                                window.set_cursor(icon);
+                            }
+                            None => window.set_cursor_visible(false),
+                        }
+                    }
                 }
             }
             _ => (),
@@ -8,6 +8,7 @@ use plutonium_engine::{
 use wgpu::Surface;
 use winit::{
     application::ApplicationHandler,
+    dpi::PhysicalSize,
     event::{ElementState, KeyEvent, WindowEvent},
     event_loop::{ActiveEventLoop, EventLoop},
     keyboard::Key,
@@ -48,14 +49,16 @@ impl<'a> ApplicationHandler<()> for TextureSvgExample<'a> {
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
         // Create the window safely with proper error handling
         let instance = wgpu::Instance::new(wgpu::InstanceDescriptor::default());
-        let window_attributes =
-            Window::default_attributes().with_title("Moveable Texture SVG Example");
+        let window_attributes = Window::default_attributes()
+            .with_title("Moveable Texture SVG Example")
+            .with_min_inner_size(PhysicalSize::new(320, 240));
         if let Ok(window) = event_loop.create_window(window_attributes) {
             let window_arc = Arc::new(window);
             let size = window_arc.as_ref().inner_size();
             let surface = instance.create_surface(window_arc.clone()).unwrap();
             let scale = window_arc.scale_factor() as f32;
             let mut engine = PlutoniumEngine::new(surface, instance, size, scale);
+            engine.set_min_size(PhysicalSize::new(320, 240));
 
             // Create the player texture
             let player: Texture2D =
@@ -78,6 +81,12 @@ impl<'a> ApplicationHandler<()> for TextureSvgExample<'a> {
             WindowEvent::CloseRequested => {
                 event_loop.exit();
             }
+            WindowEvent::Resized(new_size) => {
+                if let (Some(engine), Some(window)) = (&mut self.engine, &self.window) {
+                    engine.resize(&new_size, window.scale_factor() as f32);
+                    window.request_redraw();
+                }
+            }
             WindowEvent::CursorMoved { position, .. } => {
                 self.mouse_info.mouse_pos.x = position.x as f32;
                 self.mouse_info.mouse_pos.y = position.y as f32;
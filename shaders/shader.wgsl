@@ -1,5 +1,6 @@
 struct TransformUniform {
     transform: mat4x4<f32>,
+    opacity: f32,
 };
 
 @group(1) @binding(0)
@@ -36,5 +37,83 @@ var<uniform> uvTransform: UVTransform;
 @fragment
 fn fs_main(@location(0) tex_coords: vec2<f32>) -> @location(0) vec4<f32> {
     let adjustedTexCoords = tex_coords * uvTransform.uv_scale + uvTransform.uv_offset;
-    return textureSample(my_texture, my_sampler, adjustedTexCoords);
-}
\ No newline at end of file
+    let color = textureSample(my_texture, my_sampler, adjustedTexCoords);
+    return vec4<f32>(color.rgb, color.a * transformUniform.opacity);
+}
+
+// Every covered fragment adds the same small amount, so overlapping draws show up brighter.
+@fragment
+fn fs_overdraw(@location(0) tex_coords: vec2<f32>) -> @location(0) vec4<f32> {
+    return vec4<f32>(0.1, 0.1, 0.1, 1.0);
+}
+
+// One triangle covering the viewport; the color comes from the pipeline's blend constant.
+@vertex
+fn vs_fill(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    let corner = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    return vec4<f32>(corner * 2.0 - 1.0, 0.0, 1.0);
+}
+
+@fragment
+fn fs_fill() -> @location(0) vec4<f32> {
+    return vec4<f32>(1.0, 1.0, 1.0, 1.0);
+}
+
+// Instanced sprites carry their transform and UV rect per instance instead of in groups 1 and 2.
+struct InstanceInput {
+    @location(2) transform_0: vec4<f32>,
+    @location(3) transform_1: vec4<f32>,
+    @location(4) transform_2: vec4<f32>,
+    @location(5) transform_3: vec4<f32>,
+    @location(6) opacity: f32,
+    @location(7) uv_offset: vec2<f32>,
+    @location(8) uv_scale: vec2<f32>,
+    @location(9) color: vec4<f32>,
+};
+
+struct InstancedOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) tex_coords: vec2<f32>,
+    @location(1) opacity: f32,
+    @location(2) color: vec4<f32>,
+};
+
+@vertex
+fn vs_instanced(
+    @location(0) position: vec2<f32>,
+    @location(1) tex_coords: vec2<f32>,
+    instance: InstanceInput,
+) -> InstancedOutput {
+    let transform = mat4x4<f32>(
+        instance.transform_0,
+        instance.transform_1,
+        instance.transform_2,
+        instance.transform_3,
+    );
+    var output: InstancedOutput;
+    output.position = transform * vec4<f32>(position, 0.0, 1.0);
+    output.tex_coords = tex_coords * instance.uv_scale + instance.uv_offset;
+    output.opacity = instance.opacity;
+    output.color = instance.color;
+    return output;
+}
+
+@fragment
+fn fs_instanced(
+    @location(0) tex_coords: vec2<f32>,
+    @location(1) opacity: f32,
+    @location(2) tint: vec4<f32>,
+) -> @location(0) vec4<f32> {
+    let color = textureSample(my_texture, my_sampler, tex_coords) * tint;
+    return vec4<f32>(color.rgb, color.a * opacity);
+}
+
+// Same as fs_overdraw; it has to take every output of vs_instanced to pair with it.
+@fragment
+fn fs_instanced_overdraw(
+    @location(0) tex_coords: vec2<f32>,
+    @location(1) opacity: f32,
+    @location(2) tint: vec4<f32>,
+) -> @location(0) vec4<f32> {
+    return vec4<f32>(0.1, 0.1, 0.1, 1.0);
+}
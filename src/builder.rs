@@ -0,0 +1,97 @@
+use crate::PlutoniumEngine;
+use pollster::block_on;
+use winit::dpi::PhysicalSize;
+
+/// Configures adapter selection and surface setup before creating a `PlutoniumEngine`.
+///
+/// Options the adapter or surface can't honour fall back to the defaults `PlutoniumEngine::new`
+/// uses, so a builder never fails where `new` would succeed.
+#[derive(Debug, Clone)]
+pub struct EngineBuilder {
+    pub(crate) present_mode: wgpu::PresentMode,
+    pub(crate) sample_count: u32,
+    pub(crate) clear_color: [f32; 4],
+    pub(crate) power_preference: wgpu::PowerPreference,
+    pub(crate) surface_format: Option<wgpu::TextureFormat>,
+}
+
+impl Default for EngineBuilder {
+    fn default() -> Self {
+        EngineBuilder {
+            present_mode: wgpu::PresentMode::Fifo,
+            sample_count: 1,
+            clear_color: [0.1, 0.2, 0.3, 1.0],
+            power_preference: wgpu::PowerPreference::default(),
+            surface_format: None,
+        }
+    }
+}
+
+impl EngineBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn present_mode(mut self, present_mode: wgpu::PresentMode) -> Self {
+        self.present_mode = present_mode;
+        self
+    }
+
+    /// Samples per pixel for multisample anti-aliasing; 1 disables it.
+    pub fn msaa(mut self, sample_count: u32) -> Self {
+        self.sample_count = sample_count.max(1);
+        self
+    }
+
+    /// Color the frame is cleared to before drawing (RGBA, 0..=1).
+    pub fn clear_color(mut self, clear_color: [f32; 4]) -> Self {
+        self.clear_color = clear_color;
+        self
+    }
+
+    pub fn power_preference(mut self, power_preference: wgpu::PowerPreference) -> Self {
+        self.power_preference = power_preference;
+        self
+    }
+
+    /// Swapchain format to use if the surface supports it.
+    pub fn surface_format(mut self, surface_format: wgpu::TextureFormat) -> Self {
+        self.surface_format = Some(surface_format);
+        self
+    }
+
+    pub fn build<'a>(
+        self,
+        surface: wgpu::Surface<'a>,
+        instance: wgpu::Instance,
+        size: PhysicalSize<u32>,
+        dpi_scale_factor: f32,
+    ) -> PlutoniumEngine<'a> {
+        let adapter = block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: self.power_preference,
+            force_fallback_adapter: false,
+            // Request an adapter which can render to our surface
+            compatible_surface: Some(&surface),
+        }))
+        .expect("Failed to find an appropriate adapter");
+
+        PlutoniumEngine::from_adapter(adapter, Some(surface), size, dpi_scale_factor, &self)
+    }
+
+    /// Like `build`, but renders into an offscreen texture; see `PlutoniumEngine::new_headless`.
+    pub fn build_headless<'a>(
+        self,
+        instance: wgpu::Instance,
+        size: PhysicalSize<u32>,
+        dpi_scale_factor: f32,
+    ) -> PlutoniumEngine<'a> {
+        let adapter = block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: self.power_preference,
+            force_fallback_adapter: false,
+            compatible_surface: None,
+        }))
+        .expect("Failed to find an appropriate adapter");
+
+        PlutoniumEngine::from_adapter(adapter, None, size, dpi_scale_factor, &self)
+    }
+}
@@ -37,6 +37,25 @@ impl Camera {
             Position { x: 0.0, y: 0.0 }
         }
     }
+    /// World-space rectangle shown through a `viewport` of physical pixels at `zoom`.
+    ///
+    /// Zoom scales about the middle of the view. The boundary isn't applied here: it only
+    /// limits how far a tethered target can move before the camera follows, and `set_pos`
+    /// has already accounted for it in the position.
+    pub fn visible_world_rect(&self, viewport: Size, dpi: f32, zoom: f32) -> Rectangle {
+        let position = self.get_pos(1.0);
+        let logical_width = viewport.width / dpi;
+        let logical_height = viewport.height / dpi;
+        let width = logical_width / zoom;
+        let height = logical_height / zoom;
+        Rectangle::new(
+            position.x + (logical_width - width) / 2.0,
+            position.y + (logical_height - height) / 2.0,
+            width,
+            height,
+        )
+    }
+
     pub fn set_pos(&mut self, new_pos: Position) {
         if let Some(boundary) = &self.boundary {
             // Calculate the logical boundary taking into account both camera position and tether size
@@ -106,3 +125,34 @@ impl Camera {
         self.tether_size = size;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect_parts(rect: Rectangle) -> (f32, f32, f32, f32) {
+        (rect.x, rect.y, rect.width, rect.height)
+    }
+
+    #[test]
+    fn visible_world_rect_zooms_about_the_center() {
+        let mut camera = Camera::new(Position { x: 100.0, y: 50.0 });
+        camera.activate();
+        let viewport = Size::new(800.0, 600.0);
+
+        assert_eq!(
+            rect_parts(camera.visible_world_rect(viewport, 1.0, 1.0)),
+            (100.0, 50.0, 800.0, 600.0)
+        );
+        // half the width and height, with the same center
+        assert_eq!(
+            rect_parts(camera.visible_world_rect(viewport, 1.0, 2.0)),
+            (300.0, 200.0, 400.0, 300.0)
+        );
+        // the viewport is in physical pixels, so dpi shrinks the logical view
+        assert_eq!(
+            rect_parts(camera.visible_world_rect(viewport, 2.0, 1.0)),
+            (100.0, 50.0, 400.0, 300.0)
+        );
+    }
+}
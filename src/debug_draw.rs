@@ -0,0 +1,188 @@
+use crate::utils::{DrawParams, Position, Rectangle};
+use std::collections::HashMap;
+use std::f32::consts::TAU;
+use uuid::Uuid;
+
+const CIRCLE_SEGMENTS: usize = 32;
+const POINT_SIZE: f32 = 4.0;
+
+struct Segment {
+    from: Position,
+    to: Position,
+    thickness: f32,
+    color: [u8; 4],
+}
+
+/// Lines, outlines, circles and points drawn over everything else for a single frame.
+///
+/// Shapes are in world space and are cleared once `render` has queued them. There are no shape
+/// primitives in the pipeline, so each segment is a stretched single-pixel texture in its color.
+#[derive(Default)]
+pub struct DebugDraw {
+    segments: Vec<Segment>,
+    /// One 1x1 texture per color used so far, kept across frames.
+    pub(crate) pixels: HashMap<[u8; 4], Uuid>,
+}
+
+impl DebugDraw {
+    /// Color is RGBA, 0..=1.
+    pub fn line(&mut self, from: Position, to: Position, color: [f32; 4]) {
+        self.segments.push(Segment {
+            from,
+            to,
+            thickness: 1.0,
+            color: to_rgba8(color),
+        });
+    }
+
+    pub fn rect_outline(&mut self, rect: Rectangle, color: [f32; 4]) {
+        let top_left = rect.pos();
+        let top_right = Position {
+            x: rect.x + rect.width,
+            y: rect.y,
+        };
+        let bottom_right = Position {
+            x: rect.x + rect.width,
+            y: rect.y + rect.height,
+        };
+        let bottom_left = Position {
+            x: rect.x,
+            y: rect.y + rect.height,
+        };
+        self.line(top_left, top_right, color);
+        self.line(top_right, bottom_right, color);
+        self.line(bottom_right, bottom_left, color);
+        self.line(bottom_left, top_left, color);
+    }
+
+    pub fn circle(&mut self, center: Position, radius: f32, color: [f32; 4]) {
+        let point_at = |i: usize| {
+            let angle = TAU * i as f32 / CIRCLE_SEGMENTS as f32;
+            Position {
+                x: center.x + radius * angle.cos(),
+                y: center.y + radius * angle.sin(),
+            }
+        };
+        for i in 0..CIRCLE_SEGMENTS {
+            self.line(point_at(i), point_at(i + 1), color);
+        }
+    }
+
+    /// A small filled square centered on `position`.
+    pub fn point(&mut self, position: Position, color: [f32; 4]) {
+        self.segments.push(Segment {
+            from: Position {
+                x: position.x - POINT_SIZE / 2.0,
+                y: position.y,
+            },
+            to: Position {
+                x: position.x + POINT_SIZE / 2.0,
+                y: position.y,
+            },
+            thickness: POINT_SIZE,
+            color: to_rgba8(color),
+        });
+    }
+
+    /// Number of segments waiting for the next frame; outlines and circles count each edge.
+    pub fn len(&self) -> usize {
+        self.segments.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.segments.clear();
+    }
+
+    /// Color, top-left position and params that stretch a 1x1 texture over each segment.
+    pub(crate) fn quads(&self, dpi_scale_factor: f32) -> Vec<([u8; 4], Position, DrawParams)> {
+        self.segments
+            .iter()
+            .map(|segment| {
+                let dx = segment.to.x - segment.from.x;
+                let dy = segment.to.y - segment.from.y;
+                // the quad is stretched about its center, half a pixel in from here, so
+                // segments on whole coordinates run through pixel centers instead of between rows
+                let top_left = Position {
+                    x: (segment.from.x + segment.to.x) / 2.0,
+                    y: (segment.from.y + segment.to.y) / 2.0,
+                };
+                let params = DrawParams {
+                    rotation: dy.atan2(dx),
+                    scale_x: (dx * dx + dy * dy).sqrt() * dpi_scale_factor,
+                    scale_y: segment.thickness * dpi_scale_factor,
+                    ..Default::default()
+                };
+                (segment.color, top_left, params)
+            })
+            .collect()
+    }
+}
+
+fn to_rgba8(color: [f32; 4]) -> [u8; 4] {
+    color.map(|channel| (channel.clamp(0.0, 1.0) * 255.0).round() as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PlutoniumEngine;
+
+    const RED: [f32; 4] = [1.0, 0.0, 0.0, 1.0];
+
+    #[test]
+    fn shapes_count_their_segments_until_cleared() {
+        let mut debug_draw = DebugDraw::default();
+        assert!(debug_draw.is_empty());
+
+        let origin = Position { x: 0.0, y: 0.0 };
+        debug_draw.line(origin, Position { x: 10.0, y: 0.0 }, RED);
+        debug_draw.rect_outline(Rectangle::new(0.0, 0.0, 10.0, 10.0), RED);
+        debug_draw.circle(origin, 5.0, RED);
+        debug_draw.point(origin, RED);
+        assert_eq!(debug_draw.len(), 1 + 4 + CIRCLE_SEGMENTS + 1);
+
+        debug_draw.clear();
+        assert!(debug_draw.is_empty());
+    }
+
+    #[test]
+    fn quads_stretch_over_each_segment() {
+        let mut debug_draw = DebugDraw::default();
+        debug_draw.line(
+            Position { x: 0.0, y: 0.0 },
+            Position { x: 0.0, y: 20.0 },
+            RED,
+        );
+        let quads = debug_draw.quads(2.0);
+        let (color, center, params) = quads[0];
+        assert_eq!(color, [255, 0, 0, 255]);
+        assert_eq!((center.x, center.y), (0.0, 10.0));
+        assert_eq!((params.scale_x, params.scale_y), (40.0, 2.0));
+        assert!((params.rotation - std::f32::consts::FRAC_PI_2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn shapes_are_drawn_for_one_frame() {
+        let Some(mut engine) = PlutoniumEngine::for_tests(64, 64) else {
+            return;
+        };
+        engine
+            .debug_draw()
+            .rect_outline(Rectangle::new(8.0, 8.0, 16.0, 16.0), RED);
+        engine.render().unwrap();
+        assert!(engine.debug_draw().is_empty());
+
+        // the outline's top edge crosses the middle of the top row
+        let pixels = engine.read_pixels().unwrap();
+        let top_edge = (8 * 64 + 16) * 4;
+        assert_eq!(&pixels[top_edge..top_edge + 3], &[255, 0, 0]);
+
+        engine.render().unwrap();
+        let pixels = engine.read_pixels().unwrap();
+        assert_ne!(&pixels[top_edge..top_edge + 3], &[255, 0, 0]);
+    }
+}
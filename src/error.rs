@@ -0,0 +1,54 @@
+use std::fmt;
+
+/// Why the engine couldn't create a texture or atlas.
+#[derive(Debug)]
+pub enum EngineError {
+    /// Reading or writing a file failed.
+    Io {
+        path: String,
+        source: std::io::Error,
+    },
+    /// The SVG markup couldn't be parsed.
+    SvgParse(String),
+    /// The rasterized size is empty or larger than the GPU allows for a 2D texture.
+    TextureSize { width: u32, height: u32, max: u32 },
+    /// No pixmap can be made at this size (empty, or too large to address), so nothing was drawn.
+    RasterSize { width: u32, height: u32 },
+    /// Copying a texture back from the GPU failed.
+    Readback(String),
+    /// An atlas cache file is truncated, from another version, or made for other settings.
+    InvalidCache(String),
+    /// The adapter wouldn't hand out a new device, e.g. in `recreate_device`.
+    DeviceRequest(String),
+}
+
+impl fmt::Display for EngineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EngineError::Io { path, source } => write!(f, "{}: {}", path, source),
+            EngineError::SvgParse(message) => write!(f, "invalid SVG: {}", message),
+            EngineError::TextureSize { width, height, max } => write!(
+                f,
+                "texture size {}x{} is empty or exceeds the GPU limit of {}",
+                width, height, max
+            ),
+            EngineError::RasterSize { width, height } => {
+                write!(f, "can't rasterize at {}x{}", width, height)
+            }
+            EngineError::Readback(message) => write!(f, "texture readback failed: {}", message),
+            EngineError::InvalidCache(message) => write!(f, "invalid atlas cache: {}", message),
+            EngineError::DeviceRequest(message) => {
+                write!(f, "GPU device request failed: {}", message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for EngineError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            EngineError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
@@ -1,5 +1,8 @@
 extern crate image;
+pub mod builder;
 pub mod camera;
+pub mod debug_draw;
+pub mod error;
 pub mod pluto_objects {
     pub mod button;
     pub mod text2d;
@@ -7,12 +10,19 @@ pub mod pluto_objects {
     pub mod texture_2d;
     pub mod texture_atlas_2d;
 }
+pub mod particles;
 pub mod text;
 pub mod texture_atlas;
 pub mod texture_svg;
+pub mod tilemap;
+pub mod timing;
 pub mod traits;
 pub mod utils;
 
+use crate::builder::EngineBuilder;
+use crate::debug_draw::DebugDraw;
+use crate::error::EngineError;
+use crate::timing::FrameStats;
 use crate::traits::UpdateContext;
 use camera::Camera;
 use pluto_objects::{
@@ -26,10 +36,17 @@ use rusttype::{Font, Scale};
 
 use pollster::block_on;
 use std::cell::RefCell;
+use std::path::PathBuf;
 use std::rc::Rc;
-use std::{borrow::Cow, collections::HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+};
 use text::*;
-use texture_atlas::TextureAtlas;
+pub use texture_atlas::StaticBatch;
+use texture_atlas::{StaticBatchData, TextureAtlas, TileError};
 use texture_svg::*;
 use traits::PlutoObject;
 use utils::*;
@@ -37,29 +54,87 @@ use uuid::Uuid;
 use wgpu::util::DeviceExt;
 use winit::dpi::PhysicalSize;
 use winit::keyboard::Key;
+use winit::window::CursorIcon;
 
 enum RenderItem {
     Texture {
         texture_key: Uuid,
         transform_bind_group: wgpu::BindGroup,
+        /// Overrides the texture's own full-image UVs, e.g. for scrolling.
+        uv_bind_group: Option<wgpu::BindGroup>,
     },
     AtlasTile {
         texture_key: Uuid,
         transform_bind_group: wgpu::BindGroup,
         tile_index: usize,
     },
+    StaticBatch {
+        batch_id: Uuid,
+    },
+    /// Copies of a texture, or tiles of an atlas, drawn by the instanced pipeline.
+    Instances {
+        texture_key: Uuid,
+        instance_buffer: wgpu::Buffer,
+        instance_count: u32,
+    },
+    /// Where an `AtlasBatch` in `atlas_batches` is drawn.
+    AtlasBatch {
+        batch_index: usize,
+    },
+}
+
+/// Where a loaded font came from, so its atlas can be rebuilt (e.g. after a DPI change).
+struct FontSource {
+    path: String,
+    /// Logical font size, before DPI scaling.
+    size: f32,
+}
+
+/// A font's glyph atlas rendered on the CPU, ready to upload.
+struct RasterizedFont {
+    texture_data: Vec<u8>,
+    width: u32,
+    height: u32,
+    tile_size: Size,
+    char_map: HashMap<char, CharacterInfo>,
+    /// Physical font size.
+    font_size: f32,
+    padding: u32,
+    metrics: FontMetrics,
+}
+
+/// Callback set with `set_stats_reporter`, run every `interval` rendered frames.
+struct StatsReporter {
+    interval: u64,
+    callback: Box<dyn Fn(&FrameStats)>,
+}
+
+/// Every tile queued from one atlas while atlas batching is on, drawn in one instanced call.
+struct AtlasBatch {
+    atlas_id: Uuid,
+    instances: Vec<SpriteInstance>,
+}
+
+/// SVG markup kept around so a recolorable texture can be re-rasterized with a new color.
+struct SvgTemplate {
+    svg_data: String,
+    /// Logical scale, before DPI scaling.
+    scale_factor: f32,
 }
 
 pub struct PlutoniumEngine<'a> {
     pub size: PhysicalSize<u32>,
     dpi_scale_factor: f32,
-    surface: wgpu::Surface<'a>,
+    surface: Option<wgpu::Surface<'a>>,
+    /// Color target used instead of a swapchain when the engine is built headless.
+    offscreen_target: Option<wgpu::Texture>,
     device: wgpu::Device,
     queue: wgpu::Queue,
     config: wgpu::SurfaceConfiguration,
     render_pipeline: wgpu::RenderPipeline,
     texture_bind_group_layout: wgpu::BindGroupLayout,
     transform_bind_group_layout: wgpu::BindGroupLayout,
+    uv_bind_group_layout: wgpu::BindGroupLayout,
     texture_map: HashMap<Uuid, TextureSVG>,
     atlas_map: HashMap<Uuid, TextureAtlas>,
     pluto_objects: HashMap<Uuid, Rc<RefCell<dyn PlutoObject>>>,
@@ -68,7 +143,57 @@ pub struct PlutoniumEngine<'a> {
     viewport_size: Size,
     camera: Camera,
     text_renderer: TextRenderer,
-    loaded_fonts: HashMap<String, bool>,
+    loaded_fonts: HashMap<String, FontSource>,
+    pixel_snapping: bool,
+    virtual_resolution: Option<(Size, LetterboxMode)>,
+    min_size: PhysicalSize<u32>,
+    /// Cursor for whatever the mouse was over at the last `update`.
+    hovered_cursor_icon: CursorIcon,
+    /// Set by the app with `set_cursor_icon`; wins over the hovered object's cursor.
+    cursor_icon_override: Option<CursorIcon>,
+    cursor_visible: bool,
+    svg_templates: HashMap<Uuid, SvgTemplate>,
+    static_batches: HashMap<Uuid, StaticBatchData>,
+    /// Reason the device was lost, set from wgpu's device-lost callback.
+    device_lost: Arc<Mutex<Option<String>>>,
+    /// Which GPU and backend the engine picked, e.g. after `EngineBuilder::power_preference`.
+    adapter_info: wgpu::AdapterInfo,
+    /// Kept to request a replacement device in `recreate_device`.
+    #[cfg(feature = "retain-cpu-textures")]
+    adapter: Rc<wgpu::Adapter>,
+    #[cfg(feature = "retain-cpu-textures")]
+    settings: EngineBuilder,
+    debug_atlas_dump: Option<PathBuf>,
+    clear_color: [f32; 4],
+    sample_count: u32,
+    /// Multisampled color target resolved into the frame; `None` when MSAA is off.
+    msaa_target: Option<wgpu::Texture>,
+    debug_mode: DebugMode,
+    overdraw_pipeline: wgpu::RenderPipeline,
+    /// Only built when the adapter supports line polygon mode.
+    wireframe_pipeline: Option<wgpu::RenderPipeline>,
+    /// Draws many copies of one texture with per-instance transforms; only binds group 0.
+    instanced_pipeline: wgpu::RenderPipeline,
+    instanced_overdraw_pipeline: wgpu::RenderPipeline,
+    instanced_wireframe_pipeline: Option<wgpu::RenderPipeline>,
+    /// Paints the letterboxed viewport with the clear color, set as the blend constant.
+    fill_pipeline: wgpu::RenderPipeline,
+    frame_count: u64,
+    stats_reporter: Option<StatsReporter>,
+    /// CPU time spent in `update` and `render` since the last stats report.
+    cpu_update_time: Duration,
+    cpu_render_time: Duration,
+    auto_render: bool,
+    debug_draw: DebugDraw,
+    atlas_batching: bool,
+    atlas_batches: Vec<AtlasBatch>,
+    /// Objects drawn by the widget that owns them, so auto-render skips them.
+    child_objects: HashSet<Uuid>,
+    /// Objects hidden with `set_visible`, so hiding works even if an object keeps no flag itself.
+    hidden_objects: HashSet<Uuid>,
+    /// Cache file and rasterization scale of atlases from `create_texture_atlas_cached`, so tile
+    /// names added later are written to the cache too.
+    atlas_caches: HashMap<Uuid, (String, f32)>,
 }
 
 impl<'a> PlutoniumEngine<'a> {
@@ -98,18 +223,33 @@ impl<'a> PlutoniumEngine<'a> {
             return Ok(());
         }
 
-        let font_size = font_size * self.dpi_scale_factor;
+        let font = Self::rasterize_font(font_path, font_size * self.dpi_scale_factor)?;
+        self.install_font(font_key, font);
+
+        self.loaded_fonts.insert(
+            font_key.to_string(),
+            FontSource {
+                path: font_path.to_string(),
+                size: font_size,
+            },
+        );
+        Ok(())
+    }
+
+    /// Reads `font_path` and renders its glyph atlas at `font_size` physical pixels, without
+    /// touching the GPU, so a failure leaves the engine as it was.
+    fn rasterize_font(font_path: &str, font_size: f32) -> Result<RasterizedFont, FontError> {
         let font_data = std::fs::read(font_path).map_err(FontError::IoError)?;
         let font = Font::try_from_vec(font_data).ok_or(FontError::InvalidFontData)?;
         let scale = Scale::uniform(font_size);
         let padding = 2;
+        let v_metrics = font.v_metrics(scale);
+        let metrics = FontMetrics::new(v_metrics.ascent, v_metrics.descent, v_metrics.line_gap);
 
         // Get atlas dimensions and max tile sizes
         let (atlas_width, atlas_height, char_dimensions, max_tile_width, max_tile_height) =
             TextRenderer::calculate_atlas_size(&font, scale, padding);
 
-        let tile_size = Size::new(max_tile_width as f32, max_tile_height as f32);
-
         let (texture_data, char_map) = TextRenderer::render_glyphs_to_atlas(
             &font,
             scale,
@@ -119,32 +259,55 @@ impl<'a> PlutoniumEngine<'a> {
         )
         .ok_or(FontError::AtlasRenderError)?;
 
+        Ok(RasterizedFont {
+            texture_data,
+            width: atlas_width,
+            height: atlas_height,
+            tile_size: Size::new(max_tile_width as f32, max_tile_height as f32),
+            char_map,
+            font_size,
+            padding,
+            metrics,
+        })
+    }
+
+    /// Uploads a rasterized font and makes it the atlas behind `font_key`.
+    fn install_font(&mut self, font_key: &str, font: RasterizedFont) {
         let atlas_id = Uuid::new_v4();
         let atlas = self.create_font_texture_atlas(
             atlas_id,
-            &texture_data,
-            atlas_width,
-            atlas_height,
-            tile_size,
-            &char_map,
+            &font.texture_data,
+            font.width,
+            font.height,
+            font.tile_size,
+            &font.char_map,
         );
 
         // Pass max dimensions to store_font_atlas
         self.text_renderer.store_font_atlas(
             font_key,
             atlas,
-            char_map,
-            font_size,
-            padding,
-            Size {
-                width: max_tile_width as f32,
-                height: max_tile_height as f32,
-            },
+            font.char_map,
+            font.font_size,
+            font.padding,
+            font.tile_size,
+            font.metrics,
         );
+    }
 
-        self.loaded_fonts.insert(font_key.to_string(), true);
-        Ok(())
+    /// Vertical metrics of a loaded font in logical pixels.
+    pub fn font_metrics(&self, font_key: &str) -> Option<FontMetrics> {
+        self.text_renderer
+            .font_metrics(font_key)
+            .map(|metrics| metrics / self.dpi_scale_factor)
+    }
+
+    /// Glyphs missing from `primary_key` are taken from `fallback_key` (after any earlier fallbacks).
+    pub fn register_font_fallback(&mut self, primary_key: &str, fallback_key: &str) {
+        self.text_renderer
+            .register_font_fallback(primary_key, fallback_key);
     }
+
     pub fn set_texture_position(&mut self, key: &Uuid, position: Position) {
         if let Some(texture) = self.texture_map.get_mut(key) {
             texture.set_position(
@@ -157,21 +320,140 @@ impl<'a> PlutoniumEngine<'a> {
         }
     }
 
+    /// Applies a new DPI scale (e.g. the window moved to another monitor) and re-rasterizes
+    /// every loaded font at the new physical size so glyph metrics stay correct. Registered
+    /// objects are re-measured in the same call, so a `Text2D`'s dimensions match the new fonts.
+    pub fn set_dpi_scale_factor(&mut self, dpi_scale_factor: f32) -> Result<(), FontError> {
+        if dpi_scale_factor == self.dpi_scale_factor {
+            return Ok(());
+        }
+
+        // rasterize everything first so a missing or broken font file keeps the old atlases
+        let fonts = self
+            .loaded_fonts
+            .iter()
+            .map(|(font_key, source)| {
+                Self::rasterize_font(&source.path, source.size * dpi_scale_factor)
+                    .map(|font| (font_key.clone(), font))
+            })
+            .collect::<Result<Vec<_>, FontError>>()?;
+
+        self.dpi_scale_factor = dpi_scale_factor;
+        if self.virtual_resolution.is_none() {
+            self.viewport_size = Size {
+                width: self.size.width as f32 / dpi_scale_factor,
+                height: self.size.height as f32 / dpi_scale_factor,
+            };
+        }
+
+        for (font_key, font) in fonts {
+            if let Some(atlas_id) = self.text_renderer.font_atlas_id(&font_key) {
+                self.atlas_map.remove(&atlas_id);
+            }
+            self.install_font(&font_key, font);
+        }
+        for object in self.pluto_objects.values() {
+            object
+                .borrow_mut()
+                .rescale(&self.text_renderer, dpi_scale_factor);
+        }
+        Ok(())
+    }
+
+    pub fn dpi_scale_factor(&self) -> f32 {
+        self.dpi_scale_factor
+    }
+
     pub fn resize(&mut self, new_size: &PhysicalSize<u32>, scale_factor: f32) {
-        self.size = *new_size;
-        self.config.width = new_size.width;
-        self.config.height = new_size.height;
-        self.surface.configure(&self.device, &self.config);
+        self.size = PhysicalSize::new(
+            new_size.width.max(self.min_size.width),
+            new_size.height.max(self.min_size.height),
+        );
+        self.config.width = self.size.width;
+        self.config.height = self.size.height;
+        if let Some(surface) = &self.surface {
+            surface.configure(&self.device, &self.config);
+        } else {
+            self.offscreen_target = Some(Self::create_offscreen_target(&self.device, &self.config));
+        }
+        self.msaa_target = Self::create_msaa_target(&self.device, &self.config, self.sample_count);
+        if self.virtual_resolution.is_none() {
+            self.viewport_size = Size {
+                width: self.size.width as f32 / scale_factor,
+                height: self.size.height as f32 / scale_factor,
+            };
+        }
+    }
+
+    /// Smallest surface size `resize` will configure; smaller requests are clamped up to it.
+    ///
+    /// Pair it with winit's `with_min_inner_size` so the window itself can't shrink past it.
+    pub fn set_min_size(&mut self, min_size: PhysicalSize<u32>) {
+        self.min_size = PhysicalSize::new(min_size.width.max(1), min_size.height.max(1));
+        if self.size.width < self.min_size.width || self.size.height < self.min_size.height {
+            let size = self.size;
+            self.resize(&size, self.dpi_scale_factor);
+        }
+    }
+
+    pub fn size(&self) -> PhysicalSize<u32> {
+        self.size
+    }
+
+    /// Lays everything out in a fixed `width` x `height` space that `mode` fits into the window.
+    ///
+    /// Positions, sizes and the camera all stay in virtual units no matter how the window is
+    /// resized; the letterbox bars take the place of the clear color around the content.
+    pub fn set_virtual_resolution(&mut self, width: f32, height: f32, mode: LetterboxMode) {
+        let virtual_size = Size { width, height };
+        self.virtual_resolution = Some((virtual_size, mode));
+        self.viewport_size = virtual_size;
+    }
+
+    pub fn clear_virtual_resolution(&mut self) {
+        self.virtual_resolution = None;
         self.viewport_size = Size {
-            width: self.size.width as f32 / scale_factor,
-            height: self.size.height as f32 / scale_factor,
+            width: self.size.width as f32 / self.dpi_scale_factor,
+            height: self.size.height as f32 / self.dpi_scale_factor,
         };
     }
 
+    /// Window-pixel rect the virtual resolution is currently drawn into, if one is set.
+    pub fn letterbox_viewport(&self) -> Option<Rectangle> {
+        self.virtual_resolution.map(|(virtual_size, mode)| {
+            mode.viewport_rect(
+                virtual_size,
+                Size {
+                    width: self.size.width as f32,
+                    height: self.size.height as f32,
+                },
+            )
+        })
+    }
+
+    /// Maps a window-pixel point into virtual-resolution units; unchanged without one.
+    fn window_to_virtual(&self, window_point: Position) -> Position {
+        match (self.virtual_resolution, self.letterbox_viewport()) {
+            (Some((virtual_size, _)), Some(rect)) => Position {
+                x: (window_point.x - rect.x) * virtual_size.width / rect.width,
+                y: (window_point.y - rect.y) * virtual_size.height / rect.height,
+            },
+            _ => window_point,
+        }
+    }
+
     pub fn update(&mut self, mouse_info: Option<MouseInfo>, key: &Option<Key>) {
+        let started = Instant::now();
+        let mouse_info = mouse_info.map(|mouse| MouseInfo {
+            mouse_pos: self.window_to_virtual(mouse.mouse_pos),
+            ..mouse
+        });
         // text doesn't seem to be getting updated
         for id in &self.update_queue {
             if let Some(obj) = self.pluto_objects.get(id) {
+                if self.hidden_objects.contains(id) || !obj.borrow().is_visible() {
+                    continue;
+                }
                 obj.borrow_mut().update(
                     mouse_info,
                     key,
@@ -188,6 +470,10 @@ impl<'a> PlutoniumEngine<'a> {
             }
         }
 
+        if let Some(mouse) = mouse_info {
+            self.hovered_cursor_icon = self.hovered_cursor_icon(mouse.mouse_pos);
+        }
+
         // Handle camera tethering with DPI scaling
         let (camera_position, tether_size) = if let Some(tether_target) = &self.camera.tether_target
         {
@@ -222,20 +508,175 @@ impl<'a> PlutoniumEngine<'a> {
                 self.camera.get_pos(self.dpi_scale_factor),
             );
         }
+        self.cpu_update_time += started.elapsed();
+    }
+
+    /// Cursor the app should show, or `None` while it's hidden with `set_cursor_visible`.
+    ///
+    /// An icon from `set_cursor_icon` wins; otherwise buttons ask for a pointer and text inputs
+    /// for an I-beam at the last mouse position passed to `update`, and anywhere else is the
+    /// default. The engine doesn't own the window, so apply it with `window.set_cursor(...)` and
+    /// `window.set_cursor_visible(...)`.
+    pub fn cursor_icon(&self) -> Option<CursorIcon> {
+        self.cursor_visible.then(|| {
+            self.cursor_icon_override
+                .unwrap_or(self.hovered_cursor_icon)
+        })
+    }
+
+    /// Shows `icon` regardless of what's hovered, until `clear_cursor_icon`.
+    pub fn set_cursor_icon(&mut self, icon: CursorIcon) {
+        self.cursor_icon_override = Some(icon);
+    }
+
+    /// Goes back to choosing the cursor from the hovered object.
+    pub fn clear_cursor_icon(&mut self) {
+        self.cursor_icon_override = None;
+    }
+
+    pub fn set_cursor_visible(&mut self, visible: bool) {
+        self.cursor_visible = visible;
+    }
+
+    pub fn cursor_visible(&self) -> bool {
+        self.cursor_visible
+    }
+
+    fn hovered_cursor_icon(&self, mouse_pos: Position) -> CursorIcon {
+        self.update_queue
+            .iter()
+            .rev()
+            .filter(|id| !self.hidden_objects.contains(id))
+            .filter_map(|id| self.pluto_objects.get(id))
+            .find_map(|object| {
+                let object = object.borrow();
+                (object.is_visible() && object.dimensions().contains(mouse_pos))
+                    .then(|| object.cursor_icon())
+                    .flatten()
+            })
+            .unwrap_or_default()
     }
 
     pub fn set_camera_target(&mut self, texture_key: Uuid) {
         self.camera.tether_target = Some(texture_key);
     }
 
+    /// Color the frame is cleared to before drawing (RGBA, 0..=1).
+    pub fn set_clear_color(&mut self, clear_color: [f32; 4]) {
+        self.clear_color = clear_color;
+    }
+
+    pub fn clear_color(&self) -> [f32; 4] {
+        self.clear_color
+    }
+
+    /// Switches how `render` draws the queue. Returns false, leaving the mode unchanged, if the
+    /// adapter can't support `mode`.
+    pub fn set_debug_mode(&mut self, mode: DebugMode) -> bool {
+        if mode == DebugMode::Wireframe && self.wireframe_pipeline.is_none() {
+            return false;
+        }
+        self.debug_mode = mode;
+        true
+    }
+
+    pub fn debug_mode(&self) -> DebugMode {
+        self.debug_mode
+    }
+
+    /// Color format negotiated with the surface; every pipeline renders to this format.
+    pub fn surface_format(&self) -> wgpu::TextureFormat {
+        self.config.format
+    }
+
+    /// Samples per pixel actually in use, after falling back if the requested count was unsupported.
+    pub fn sample_count(&self) -> u32 {
+        self.sample_count
+    }
+
+    /// Rounds queued sprite and tile positions to whole physical pixels so slow movement doesn't shimmer.
+    pub fn set_pixel_snapping(&mut self, enabled: bool) {
+        self.pixel_snapping = enabled;
+    }
+
+    /// Snaps a physical position so its on-screen offset from the camera is a whole pixel.
+    fn snap_position(&self, position: Position) -> Position {
+        if !self.pixel_snapping {
+            return position;
+        }
+        let camera_position = self.camera.get_pos(self.dpi_scale_factor);
+        Position {
+            x: (position.x - camera_position.x).round() + camera_position.x,
+            y: (position.y - camera_position.y).round() + camera_position.y,
+        }
+    }
+
     pub fn queue_texture(&mut self, texture_key: &Uuid, position: Option<Position>) {
+        self.queue_texture_with_params(texture_key, position, DrawParams::default());
+    }
+
+    pub fn queue_texture_with_params(
+        &mut self,
+        texture_key: &Uuid,
+        position: Option<Position>,
+        params: DrawParams,
+    ) {
+        self.queue_texture_with_uv(texture_key, position, params, None);
+    }
+
+    /// Sets how a texture or atlas samples UVs outside 0..=1; `Repeat` lets scrolled textures wrap.
+    ///
+    /// Returns false if `texture_key` is neither a texture nor an atlas.
+    pub fn set_texture_address_mode(
+        &mut self,
+        texture_key: &Uuid,
+        address_mode: AddressMode,
+    ) -> bool {
+        if let Some(texture) = self.texture_map.get_mut(texture_key) {
+            texture.set_address_mode(&self.device, &self.texture_bind_group_layout, address_mode);
+        } else if let Some(atlas) = self.atlas_map.get_mut(texture_key) {
+            atlas.set_address_mode(&self.device, &self.texture_bind_group_layout, address_mode);
+        } else {
+            return false;
+        }
+        true
+    }
+
+    /// Queues a texture sampled at `uv * uv_scale + uv_offset`, so animating `uv_offset` scrolls
+    /// the image inside its quad. Offsets past the edge need a repeating sampler to wrap.
+    pub fn queue_texture_scrolled(
+        &mut self,
+        texture_key: &Uuid,
+        position: Position,
+        uv_offset: [f32; 2],
+        uv_scale: [f32; 2],
+    ) {
+        self.queue_texture_with_uv(
+            texture_key,
+            Some(position),
+            DrawParams::default(),
+            Some(UVTransform {
+                uv_offset,
+                uv_scale,
+            }),
+        );
+    }
+
+    fn queue_texture_with_uv(
+        &mut self,
+        texture_key: &Uuid,
+        position: Option<Position>,
+        params: DrawParams,
+        uv_transform: Option<UVTransform>,
+    ) {
         if let Some(texture) = self.texture_map.get(texture_key) {
             // Generate the transformation matrix based on the position and camera
-            let position = position.unwrap_or_default() * self.dpi_scale_factor;
+            let position = self.snap_position(position.unwrap_or_default() * self.dpi_scale_factor);
             let transform_uniform = texture.get_transform_uniform(
                 self.viewport_size,
                 position,
                 self.camera.get_pos(self.dpi_scale_factor),
+                &params,
             );
 
             let transform_uniform_buffer =
@@ -259,23 +700,78 @@ impl<'a> PlutoniumEngine<'a> {
                 label: Some("Transform Bind Group"),
             });
 
+            let uv_bind_group = uv_transform.map(|uv_transform| {
+                let uv_buffer = self
+                    .device
+                    .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                        label: Some("UV Uniform Buffer"),
+                        contents: bytemuck::bytes_of(&uv_transform),
+                        usage: wgpu::BufferUsages::UNIFORM,
+                    });
+                self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    layout: &self.uv_bind_group_layout,
+                    entries: &[wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: uv_buffer.as_entire_binding(),
+                    }],
+                    label: Some("UV Bind Group"),
+                })
+            });
+
             self.render_queue.push(RenderItem::Texture {
                 texture_key: *texture_key,
                 transform_bind_group,
+                uv_bind_group,
             });
         }
     }
 
     pub fn queue_tile(&mut self, texture_key: &Uuid, tile_index: usize, position: Position) {
-        let position = position * self.dpi_scale_factor;
+        self.queue_tile_with_params(texture_key, tile_index, position, DrawParams::default());
+    }
+
+    pub fn queue_tile_with_params(
+        &mut self,
+        texture_key: &Uuid,
+        tile_index: usize,
+        position: Position,
+        params: DrawParams,
+    ) {
+        let position = self.snap_position(position * self.dpi_scale_factor);
         if let Some(atlas) = self.atlas_map.get(texture_key) {
             // Get transform from TextureAtlas
             let transform_uniform = atlas.get_transform_uniform(
                 self.viewport_size,
                 position,
                 self.camera.get_pos(self.dpi_scale_factor),
+                &params,
             );
 
+            if self.atlas_batching {
+                let instance = SpriteInstance::new(&transform_uniform, atlas.tile_uv(tile_index));
+                // only extend a batch nothing else has been queued after, so draw order holds
+                let open_batch = match self.render_queue.last() {
+                    Some(RenderItem::AtlasBatch { batch_index }) => {
+                        Some(&mut self.atlas_batches[*batch_index])
+                            .filter(|batch| batch.atlas_id == *texture_key)
+                    }
+                    _ => None,
+                };
+                match open_batch {
+                    Some(batch) => batch.instances.push(instance),
+                    None => {
+                        self.render_queue.push(RenderItem::AtlasBatch {
+                            batch_index: self.atlas_batches.len(),
+                        });
+                        self.atlas_batches.push(AtlasBatch {
+                            atlas_id: *texture_key,
+                            instances: vec![instance],
+                        });
+                    }
+                }
+                return;
+            }
+
             let transform_uniform_buffer =
                 self.device
                     .create_buffer_init(&wgpu::util::BufferInitDescriptor {
@@ -305,12 +801,231 @@ impl<'a> PlutoniumEngine<'a> {
         }
     }
 
+    /// Keeps a fixed set of atlas tiles in one GPU instance buffer, so `draw_static_batch` draws
+    /// them all in a single instanced call instead of one draw per tile like `queue_tile`.
+    ///
+    /// Positions are in the same units as `queue_tile`. The buffer is only rewritten when the
+    /// camera, viewport or pixel snapping has changed since the last draw.
+    pub fn build_static_atlas_batch(
+        &mut self,
+        atlas_id: &Uuid,
+        instances: &[(usize, Position)],
+    ) -> StaticBatch {
+        let tiles: Vec<(usize, Position)> = instances
+            .iter()
+            .map(|(tile_index, position)| (*tile_index, *position * self.dpi_scale_factor))
+            .collect();
+        let instance_buffer = Self::create_static_batch_buffer(&self.device, tiles.len());
+
+        let id = Uuid::new_v4();
+        self.static_batches.insert(
+            id,
+            StaticBatchData {
+                atlas_id: *atlas_id,
+                tiles,
+                instance_buffer,
+                uploaded_for: None,
+                uploads: 0,
+            },
+        );
+        StaticBatch { id }
+    }
+
+    /// Vertex buffer with room for one `SpriteInstance` per tile.
+    fn create_static_batch_buffer(device: &wgpu::Device, tile_count: usize) -> wgpu::Buffer {
+        device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Static Batch Instance Buffer"),
+            size: (std::mem::size_of::<SpriteInstance>() * tile_count.max(1)) as u64,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        })
+    }
+
+    /// Queues a static batch as one instanced draw, rewriting its instances only if the view,
+    /// or pixel snapping, changed. Snapping is applied here rather than when the batch is built.
+    pub fn draw_static_batch(&mut self, batch: &StaticBatch) {
+        let camera_position = self.camera.get_pos(self.dpi_scale_factor);
+        let view = (self.viewport_size, camera_position, self.pixel_snapping);
+        let Some(data) = self.static_batches.get(&batch.id) else {
+            return;
+        };
+        let Some(atlas) = self.atlas_map.get(&data.atlas_id) else {
+            return;
+        };
+        if data.uploaded_for != Some(view) {
+            let instances: Vec<SpriteInstance> = data
+                .tiles
+                .iter()
+                .map(|(tile_index, position)| {
+                    let transform_uniform = atlas.get_transform_uniform(
+                        self.viewport_size,
+                        self.snap_position(*position),
+                        camera_position,
+                        &DrawParams::default(),
+                    );
+                    SpriteInstance::new(&transform_uniform, atlas.tile_uv(*tile_index))
+                })
+                .collect();
+            self.queue
+                .write_buffer(&data.instance_buffer, 0, bytemuck::cast_slice(&instances));
+            if let Some(data) = self.static_batches.get_mut(&batch.id) {
+                data.uploaded_for = Some(view);
+                data.uploads += 1;
+            }
+        }
+        self.render_queue
+            .push(RenderItem::StaticBatch { batch_id: batch.id });
+    }
+
+    /// How many times the batch's transforms have been written to the GPU.
+    pub fn static_batch_uploads(&self, batch: &StaticBatch) -> Option<u64> {
+        self.static_batches.get(&batch.id).map(|data| data.uploads)
+    }
+
+    pub fn remove_static_batch(&mut self, batch: StaticBatch) {
+        self.static_batches.remove(&batch.id);
+    }
+
+    /// Like `queue_tile`, but reports a missing atlas or out-of-range tile instead of drawing nothing.
+    pub fn try_queue_tile(
+        &mut self,
+        texture_key: &Uuid,
+        tile_index: usize,
+        position: Position,
+    ) -> Result<(), TileError> {
+        let count = self
+            .atlas_map
+            .get(texture_key)
+            .ok_or(TileError::AtlasNotFound)?
+            .tile_count();
+        if tile_index >= count {
+            return Err(TileError::TileIndexOutOfRange {
+                index: tile_index,
+                count,
+            });
+        }
+        self.queue_tile(texture_key, tile_index, position);
+        Ok(())
+    }
+
+    /// Draws one copy of a texture per `(position, params)` with a single instanced draw call.
+    ///
+    /// Positions and params mean the same as in `queue_texture_with_params`. The copies are one
+    /// item in the render queue, so they keep their place relative to other queued draws.
+    pub fn draw_sprite_instances(
+        &mut self,
+        texture_key: &Uuid,
+        transforms: &[(Position, DrawParams)],
+    ) {
+        let Some(texture) = self.texture_map.get(texture_key) else {
+            return;
+        };
+        if transforms.is_empty() {
+            return;
+        }
+        let camera_position = self.camera.get_pos(self.dpi_scale_factor);
+        let full_texture = UVTransform {
+            uv_offset: [0.0, 0.0],
+            uv_scale: [1.0, 1.0],
+        };
+        let instances: Vec<SpriteInstance> = transforms
+            .iter()
+            .map(|(position, params)| {
+                let transform_uniform = texture.get_transform_uniform(
+                    self.viewport_size,
+                    self.snap_position(*position * self.dpi_scale_factor),
+                    camera_position,
+                    params,
+                );
+                SpriteInstance::new(&transform_uniform, full_texture)
+            })
+            .collect();
+        self.queue_instances(*texture_key, &instances);
+    }
+
+    /// Draws one atlas tile per `(tile_index, position, params, color)` with a single instanced
+    /// draw call, each tinted by its RGBA color (0..=1, white for none).
+    pub fn draw_tile_instances(
+        &mut self,
+        atlas_id: &Uuid,
+        tiles: &[(usize, Position, DrawParams, [f32; 4])],
+    ) {
+        let Some(atlas) = self.atlas_map.get(atlas_id) else {
+            return;
+        };
+        if tiles.is_empty() {
+            return;
+        }
+        let camera_position = self.camera.get_pos(self.dpi_scale_factor);
+        let instances: Vec<SpriteInstance> = tiles
+            .iter()
+            .map(|(tile_index, position, params, color)| {
+                let transform_uniform = atlas.get_transform_uniform(
+                    self.viewport_size,
+                    self.snap_position(*position * self.dpi_scale_factor),
+                    camera_position,
+                    params,
+                );
+                SpriteInstance {
+                    color: *color,
+                    ..SpriteInstance::new(&transform_uniform, atlas.tile_uv(*tile_index))
+                }
+            })
+            .collect();
+        self.queue_instances(*atlas_id, &instances);
+    }
+
+    fn queue_instances(&mut self, texture_key: Uuid, instances: &[SpriteInstance]) {
+        let instance_buffer = self
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Sprite Instance Buffer"),
+                contents: bytemuck::cast_slice(instances),
+                usage: wgpu::BufferUsages::VERTEX,
+            });
+        self.render_queue.push(RenderItem::Instances {
+            texture_key,
+            instance_buffer,
+            instance_count: instances.len() as u32,
+        });
+    }
+
+    /// Queues many textures in one pass, growing the render queue once up front.
+    ///
+    /// Each item's transform lives in its render queue entry, so that single reservation covers
+    /// the transforms as well.
+    pub fn draw_sprites_batch(&mut self, items: &[(Uuid, Position, DrawParams)]) {
+        self.render_queue.reserve(items.len());
+        for (texture_key, position, params) in items {
+            self.queue_texture_with_params(texture_key, Some(*position), *params);
+        }
+    }
+
+    /// Queues many atlas tiles in one pass, growing the render queue once up front.
+    pub fn draw_tiles_batch(&mut self, items: &[(Uuid, usize, Position, DrawParams)]) {
+        self.render_queue.reserve(items.len());
+        for (texture_key, tile_index, position, params) in items {
+            self.queue_tile_with_params(texture_key, *tile_index, *position, *params);
+        }
+    }
+
     pub fn queue_text(&mut self, text: &str, font_key: &str, position: Position) {
-        let chars = self.text_renderer.calculate_text_layout(
+        self.queue_text_with_line_height(text, font_key, position, LineHeight::default());
+    }
+
+    pub fn queue_text_with_line_height(
+        &mut self,
+        text: &str,
+        font_key: &str,
+        position: Position,
+        line_height: LineHeight,
+    ) {
+        let chars = self.text_renderer.calculate_text_layout_with_line_height(
             text,
             font_key,
             position,
             self.dpi_scale_factor,
+            line_height,
         );
         for char in chars {
             // Scale position here instead
@@ -320,55 +1035,271 @@ impl<'a> PlutoniumEngine<'a> {
         }
     }
 
+    /// Queues text like `queue_text`, displacing each glyph by `offset_fn(char_index, character)`.
+    pub fn queue_text_with_offsets(
+        &mut self,
+        text: &str,
+        font_key: &str,
+        position: Position,
+        offset_fn: impl Fn(usize, char) -> Position,
+    ) {
+        let chars = self.text_renderer.calculate_text_layout(
+            text,
+            font_key,
+            position,
+            self.dpi_scale_factor,
+        );
+        for char in chars {
+            let offset = offset_fn(char.char_index, char.character);
+            let offset_position = Position {
+                x: char.position.x + offset.x,
+                y: char.position.y + offset.y,
+            };
+            self.queue_tile(&char.atlas_id, char.tile_index, offset_position);
+        }
+    }
+
     pub fn clear_render_queue(&mut self) {
         self.render_queue.clear();
+        self.atlas_batches.clear();
+    }
+
+    /// Draws each run of tiles queued back to back from the same atlas as one instanced call,
+    /// tilemaps and text included.
+    ///
+    /// Queuing anything else in between starts a new batch, so draw order is the same as with
+    /// batching off; interleaving two atlases tile by tile gets no benefit.
+    pub fn set_atlas_batching(&mut self, atlas_batching: bool) {
+        self.atlas_batching = atlas_batching;
+    }
+
+    pub fn atlas_batching(&self) -> bool {
+        self.atlas_batching
+    }
+
+    /// True once the GPU device has been lost (driver reset, GPU removed, ...).
+    ///
+    /// Every texture and buffer is gone at that point. With the `retain-cpu-textures` feature,
+    /// `recreate_device` rebuilds them on a new device; otherwise the app has to create a fresh
+    /// engine and reload its assets.
+    pub fn is_device_lost(&self) -> bool {
+        self.device_lost_reason().is_some()
+    }
+
+    pub fn adapter_info(&self) -> &wgpu::AdapterInfo {
+        &self.adapter_info
+    }
+
+    /// Why the device was lost, as reported by the driver; `None` while it is healthy.
+    pub fn device_lost_reason(&self) -> Option<String> {
+        self.device_lost
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    /// Writes each glyph atlas to `path` as a PNG when fonts load; `None` (the default) disables it.
+    ///
+    /// Write failures are reported and otherwise ignored so a bad path can't break font loading.
+    pub fn set_debug_atlas_dump(&mut self, path: Option<PathBuf>) {
+        self.debug_atlas_dump = path;
+    }
+
+    /// Requests a new device from the same adapter, rebuilds the surface setup and pipelines on
+    /// it, and re-uploads every texture and atlas from its retained CPU pixels.
+    ///
+    /// Meant for after `is_device_lost`; objects, fonts and static batches keep their ids. Draws
+    /// queued before the call are dropped.
+    #[cfg(feature = "retain-cpu-textures")]
+    pub fn recreate_device(&mut self) -> Result<(), EngineError> {
+        let (device, queue) = Self::request_device(&self.adapter)
+            .map_err(|err| EngineError::DeviceRequest(err.to_string()))?;
+        let rebuilt = Self::from_device(
+            Rc::clone(&self.adapter),
+            device,
+            queue,
+            self.surface.take(),
+            self.size,
+            self.dpi_scale_factor,
+            &self.settings,
+        );
+        self.surface = rebuilt.surface;
+        self.offscreen_target = rebuilt.offscreen_target;
+        self.device = rebuilt.device;
+        self.queue = rebuilt.queue;
+        self.config = rebuilt.config;
+        self.render_pipeline = rebuilt.render_pipeline;
+        self.texture_bind_group_layout = rebuilt.texture_bind_group_layout;
+        self.transform_bind_group_layout = rebuilt.transform_bind_group_layout;
+        self.uv_bind_group_layout = rebuilt.uv_bind_group_layout;
+        self.device_lost = rebuilt.device_lost;
+        self.sample_count = rebuilt.sample_count;
+        self.msaa_target = rebuilt.msaa_target;
+        self.overdraw_pipeline = rebuilt.overdraw_pipeline;
+        self.wireframe_pipeline = rebuilt.wireframe_pipeline;
+        self.instanced_pipeline = rebuilt.instanced_pipeline;
+        self.instanced_overdraw_pipeline = rebuilt.instanced_overdraw_pipeline;
+        self.instanced_wireframe_pipeline = rebuilt.instanced_wireframe_pipeline;
+        self.fill_pipeline = rebuilt.fill_pipeline;
+
+        // queued items hold bind groups and buffers from the old device
+        self.render_queue.clear();
+        self.atlas_batches.clear();
+        for texture in self.texture_map.values_mut() {
+            texture.reupload(
+                &self.device,
+                &self.queue,
+                &self.texture_bind_group_layout,
+                &self.transform_bind_group_layout,
+            );
+        }
+        for atlas in self.atlas_map.values_mut() {
+            atlas.reupload(
+                &self.device,
+                &self.queue,
+                &self.texture_bind_group_layout,
+                &self.transform_bind_group_layout,
+            );
+        }
+        for data in self.static_batches.values_mut() {
+            data.instance_buffer = Self::create_static_batch_buffer(&self.device, data.tiles.len());
+            data.uploaded_for = None;
+        }
+        Ok(())
     }
 
     pub fn render(&mut self) -> Result<(), wgpu::SurfaceError> {
-        let frame = self
-            .surface
-            .get_current_texture()
-            .expect("Failed to acquire next swap chain texture");
-        let view = frame
-            .texture
-            .create_view(&wgpu::TextureViewDescriptor::default());
+        let started = Instant::now();
+        let batch_sizes: Vec<usize> = self
+            .atlas_batches
+            .iter()
+            .map(|batch| batch.instances.len())
+            .collect();
+        let auto_rendered = self.queue_auto_render();
+        let debug_drawn = self.queue_debug_draw();
+        let frame = self.surface.as_ref().map(|surface| {
+            surface
+                .get_current_texture()
+                .expect("Failed to acquire next swap chain texture")
+        });
+        let target_texture = match (&frame, &self.offscreen_target) {
+            (Some(frame), _) => &frame.texture,
+            (None, Some(offscreen_target)) => offscreen_target,
+            (None, None) => unreachable!("engine has neither a surface nor an offscreen target"),
+        };
+        let view = target_texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let msaa_view = self
+            .msaa_target
+            .as_ref()
+            .map(|msaa_target| msaa_target.create_view(&wgpu::TextureViewDescriptor::default()));
+        let clear_color = match (self.debug_mode, self.virtual_resolution) {
+            (DebugMode::Overdraw, _) => [0.0, 0.0, 0.0, 1.0],
+            (_, Some((_, LetterboxMode::Letterbox { bar_color }))) => bar_color,
+            _ => self.clear_color,
+        };
+        let render_pipeline = match (self.debug_mode, &self.wireframe_pipeline) {
+            (DebugMode::Overdraw, _) => &self.overdraw_pipeline,
+            (DebugMode::Wireframe, Some(wireframe_pipeline)) => wireframe_pipeline,
+            _ => &self.render_pipeline,
+        };
+        let instanced_pipeline = match (self.debug_mode, &self.instanced_wireframe_pipeline) {
+            (DebugMode::Overdraw, _) => &self.instanced_overdraw_pipeline,
+            (DebugMode::Wireframe, Some(wireframe_pipeline)) => wireframe_pipeline,
+            _ => &self.instanced_pipeline,
+        };
+        let clear_color = wgpu::Color {
+            r: clear_color[0] as f64,
+            g: clear_color[1] as f64,
+            b: clear_color[2] as f64,
+            a: clear_color[3] as f64,
+        };
+
+        let batch_buffers: Vec<wgpu::Buffer> = self
+            .atlas_batches
+            .iter()
+            .map(|batch| {
+                self.device
+                    .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                        label: Some("Atlas Batch Instance Buffer"),
+                        contents: bytemuck::cast_slice(&batch.instances),
+                        usage: wgpu::BufferUsages::VERTEX,
+                    })
+            })
+            .collect();
 
         let mut encoder = self
             .device
             .create_command_encoder(&wgpu::CommandEncoderDescriptor {
                 label: Some("Render Encoder"),
             });
+        let mut draw_calls = 0;
 
         {
             let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 label: Some("Render Pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &view,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color {
-                            r: 0.1,
-                            g: 0.2,
-                            b: 0.3,
-                            a: 1.0,
-                        }),
-                        store: wgpu::StoreOp::Store,
+                color_attachments: &[Some(match &msaa_view {
+                    // render multisampled and resolve into the frame; the samples aren't needed after
+                    Some(msaa_view) => wgpu::RenderPassColorAttachment {
+                        view: msaa_view,
+                        resolve_target: Some(&view),
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Clear(clear_color),
+                            store: wgpu::StoreOp::Discard,
+                        },
                     },
-                })],
+                    None => wgpu::RenderPassColorAttachment {
+                        view: &view,
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Clear(clear_color),
+                            store: wgpu::StoreOp::Store,
+                        },
+                    },
+                })],
                 depth_stencil_attachment: None,
                 timestamp_writes: None,
                 occlusion_query_set: None,
             });
 
+            if let Some(rect) = self.letterbox_viewport() {
+                rpass.set_viewport(rect.x, rect.y, rect.width, rect.height, 0.0, 1.0);
+                // the pass cleared to the bar color, so paint the clear color back inside the bars
+                if matches!(
+                    self.virtual_resolution,
+                    Some((_, LetterboxMode::Letterbox { .. }))
+                ) && self.debug_mode != DebugMode::Overdraw
+                {
+                    let [r, g, b, a] = self.clear_color.map(|channel| channel as f64);
+                    rpass.set_pipeline(&self.fill_pipeline);
+                    rpass.set_blend_constant(wgpu::Color { r, g, b, a });
+                    rpass.draw(0..3, 0..1);
+                    draw_calls += 1;
+                }
+            }
+
             for item in &self.render_queue {
                 match item {
                     RenderItem::Texture {
                         texture_key,
                         transform_bind_group,
+                        uv_bind_group,
                     } => {
                         // Render the texture, using the precomputed transform
                         if let Some(texture) = self.texture_map.get(texture_key) {
-                            texture.render(&mut rpass, &self.render_pipeline, transform_bind_group);
+                            match uv_bind_group {
+                                Some(uv_bind_group) => texture.render_with_uv(
+                                    &mut rpass,
+                                    render_pipeline,
+                                    transform_bind_group,
+                                    uv_bind_group,
+                                ),
+                                None => texture.render(
+                                    &mut rpass,
+                                    render_pipeline,
+                                    transform_bind_group,
+                                ),
+                            }
+                            draw_calls += 1;
                         }
                     }
                     RenderItem::AtlasTile {
@@ -379,28 +1310,329 @@ impl<'a> PlutoniumEngine<'a> {
                         if let Some(atlas) = self.atlas_map.get(texture_key) {
                             atlas.render_tile(
                                 &mut rpass,
-                                &self.render_pipeline,
+                                render_pipeline,
                                 *tile_index,
                                 transform_bind_group,
                             );
+                            draw_calls += 1;
+                        }
+                    }
+                    RenderItem::Instances {
+                        texture_key,
+                        instance_buffer,
+                        instance_count,
+                    } => {
+                        if let Some(texture) = self.texture_map.get(texture_key) {
+                            texture.render_instanced(
+                                &mut rpass,
+                                instanced_pipeline,
+                                instance_buffer,
+                                *instance_count,
+                            );
+                            draw_calls += 1;
+                        } else if let Some(atlas) = self.atlas_map.get(texture_key) {
+                            atlas.render_instanced(
+                                &mut rpass,
+                                instanced_pipeline,
+                                instance_buffer,
+                                *instance_count,
+                            );
+                            draw_calls += 1;
+                        }
+                    }
+                    RenderItem::AtlasBatch { batch_index } => {
+                        let batch = &self.atlas_batches[*batch_index];
+                        if let Some(atlas) = self.atlas_map.get(&batch.atlas_id) {
+                            atlas.render_instanced(
+                                &mut rpass,
+                                instanced_pipeline,
+                                &batch_buffers[*batch_index],
+                                batch.instances.len() as u32,
+                            );
+                            draw_calls += 1;
+                        }
+                    }
+                    RenderItem::StaticBatch { batch_id } => {
+                        let Some(batch) = self.static_batches.get(batch_id) else {
+                            continue;
+                        };
+                        if batch.tiles.is_empty() {
+                            continue;
+                        }
+                        if let Some(atlas) = self.atlas_map.get(&batch.atlas_id) {
+                            atlas.render_instanced(
+                                &mut rpass,
+                                instanced_pipeline,
+                                &batch.instance_buffer,
+                                batch.tiles.len() as u32,
+                            );
+                            draw_calls += 1;
                         }
                     }
                 }
             }
         }
         self.queue.submit(Some(encoder.finish()));
-        frame.present();
+        // stop before present, which can block on vsync rather than doing CPU work
+        self.cpu_render_time += started.elapsed();
+        if let Some(frame) = frame {
+            frame.present();
+        }
+
+        self.frame_count += 1;
+        if let Some(reporter) = &self.stats_reporter {
+            if self.frame_count.is_multiple_of(reporter.interval) {
+                let per_frame_ms =
+                    |total: Duration| total.as_secs_f32() * 1000.0 / reporter.interval as f32;
+                (reporter.callback)(&FrameStats {
+                    frame: self.frame_count,
+                    draw_calls,
+                    textures: self.texture_map.len(),
+                    atlases: self.atlas_map.len(),
+                    objects: self.pluto_objects.len(),
+                    cpu_update_ms: per_frame_ms(self.cpu_update_time),
+                    cpu_render_ms: per_frame_ms(self.cpu_render_time),
+                });
+                self.cpu_update_time = Duration::ZERO;
+                self.cpu_render_time = Duration::ZERO;
+            }
+        }
+        // retained objects and debug shapes are re-queued every frame, so they never pile up
+        self.render_queue
+            .truncate(self.render_queue.len() - debug_drawn);
+        self.render_queue.drain(..auto_rendered);
+        self.atlas_batches.truncate(batch_sizes.len());
+        for (batch, size) in self.atlas_batches.iter_mut().zip(batch_sizes) {
+            batch.instances.truncate(size);
+        }
         Ok(())
     }
 
+    /// Queues every registered visible object each frame in `render`, beneath anything queued
+    /// by hand, so apps that only use created objects never touch the render queue.
+    pub fn set_auto_render(&mut self, auto_render: bool) {
+        self.auto_render = auto_render;
+    }
+
+    pub fn auto_render(&self) -> bool {
+        self.auto_render
+    }
+
+    /// Shapes drawn on top of everything in the next `render`, then cleared.
+    pub fn debug_draw(&mut self) -> &mut DebugDraw {
+        &mut self.debug_draw
+    }
+
+    /// Queues pending debug shapes after everything else; returns how many items that added.
+    fn queue_debug_draw(&mut self) -> usize {
+        let quads = self.debug_draw.quads(self.dpi_scale_factor);
+        self.debug_draw.clear();
+        for (color, position, params) in &quads {
+            let texture_key = match self.debug_draw.pixels.get(color) {
+                Some(texture_key) => *texture_key,
+                None => {
+                    let pixel = image::RgbaImage::from_pixel(1, 1, image::Rgba(*color));
+                    let (texture_key, _) =
+                        self.create_texture_from_rgba(&pixel, Position::default());
+                    // size the quad now, since the next `update` comes after this frame
+                    if let Some(texture) = self.texture_map.get_mut(&texture_key) {
+                        texture.update_transform_uniform(
+                            &self.device,
+                            &self.queue,
+                            self.viewport_size,
+                            self.camera.get_pos(self.dpi_scale_factor),
+                        );
+                    }
+                    self.debug_draw.pixels.insert(*color, texture_key);
+                    texture_key
+                }
+            };
+            self.queue_texture_with_params(&texture_key, Some(*position), *params);
+        }
+        quads.len()
+    }
+
+    /// Puts auto-rendered objects at the front of the queue; returns how many items that added.
+    fn queue_auto_render(&mut self) -> usize {
+        if !self.auto_render {
+            return 0;
+        }
+        let immediate = std::mem::take(&mut self.render_queue);
+        let objects: Vec<_> = self
+            .update_queue
+            .iter()
+            .filter(|id| !self.child_objects.contains(id) && !self.hidden_objects.contains(id))
+            .filter_map(|id| self.pluto_objects.get(id).cloned())
+            .collect();
+        for object in objects {
+            object.borrow().render(self);
+        }
+        let auto_rendered = self.render_queue.len();
+        self.render_queue.extend(immediate);
+        auto_rendered
+    }
+
+    /// Calls `reporter` with the current frame's stats every `interval_frames` rendered frames,
+    /// e.g. to feed a log or an on-screen HUD. Replaces any previous reporter.
+    pub fn set_stats_reporter(
+        &mut self,
+        interval_frames: u32,
+        reporter: impl Fn(&FrameStats) + 'static,
+    ) {
+        self.stats_reporter = Some(StatsReporter {
+            interval: interval_frames.max(1) as u64,
+            callback: Box::new(reporter),
+        });
+        self.cpu_update_time = Duration::ZERO;
+        self.cpu_render_time = Duration::ZERO;
+    }
+
+    pub fn clear_stats_reporter(&mut self) {
+        self.stats_reporter = None;
+    }
+
+    /// Reads back the last rendered frame of a headless engine as tightly packed RGBA8 rows.
+    pub fn read_pixels(&self) -> Result<Vec<u8>, EngineError> {
+        let target = self.offscreen_target.as_ref().ok_or_else(|| {
+            EngineError::Readback("read_pixels is only available on headless engines".to_string())
+        })?;
+
+        // the target is at least 1x1 even when the configured size is zero
+        let width = target.width();
+        let height = target.height();
+        let unpadded_bytes_per_row = width * 4;
+        let padded_bytes_per_row = unpadded_bytes_per_row
+            .div_ceil(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT)
+            * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+
+        let output_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Headless Readback Buffer"),
+            size: padded_bytes_per_row as u64 * height as u64,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Headless Readback Encoder"),
+            });
+        encoder.copy_texture_to_buffer(
+            wgpu::ImageCopyTexture {
+                texture: target,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::ImageCopyBuffer {
+                buffer: &output_buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_bytes_per_row),
+                    rows_per_image: Some(height),
+                },
+            },
+            wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+        );
+        self.queue.submit(std::iter::once(encoder.finish()));
+
+        let buffer_slice = output_buffer.slice(..);
+        let (tx, rx) = std::sync::mpsc::channel();
+        buffer_slice.map_async(wgpu::MapMode::Read, move |result| {
+            tx.send(result).unwrap();
+        });
+        self.device.poll(wgpu::Maintain::Wait);
+        rx.recv()
+            .map_err(|e| EngineError::Readback(format!("Failed to receive mapping result: {}", e)))?
+            .map_err(|e| EngineError::Readback(e.to_string()))?;
+
+        let padded_data = buffer_slice.get_mapped_range();
+        let mut pixels = Vec::with_capacity((unpadded_bytes_per_row * height) as usize);
+        for row in padded_data.chunks(padded_bytes_per_row as usize) {
+            pixels.extend_from_slice(&row[..unpadded_bytes_per_row as usize]);
+        }
+        drop(padded_data);
+        output_buffer.unmap();
+
+        // the offscreen target shares the swapchain's BGRA format
+        if matches!(
+            self.config.format,
+            wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb
+        ) {
+            for pixel in pixels.chunks_exact_mut(4) {
+                pixel.swap(0, 2);
+            }
+        }
+
+        Ok(pixels)
+    }
+
+    fn create_offscreen_target(
+        device: &wgpu::Device,
+        config: &wgpu::SurfaceConfiguration,
+    ) -> wgpu::Texture {
+        device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Headless Color Target"),
+            size: wgpu::Extent3d {
+                width: config.width.max(1),
+                height: config.height.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: config.format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        })
+    }
+
+    fn create_msaa_target(
+        device: &wgpu::Device,
+        config: &wgpu::SurfaceConfiguration,
+        sample_count: u32,
+    ) -> Option<wgpu::Texture> {
+        if sample_count <= 1 {
+            return None;
+        }
+        Some(device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("MSAA Color Target"),
+            size: wgpu::Extent3d {
+                width: config.width.max(1),
+                height: config.height.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count,
+            dimension: wgpu::TextureDimension::D2,
+            format: config.format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        }))
+    }
+
     pub fn create_texture_svg(
         &mut self,
         file_path: &str,
         position: Position,
         scale_factor: f32,
     ) -> (Uuid, Rectangle) {
+        self.try_create_texture_svg(file_path, position, scale_factor)
+            .expect("texture should always be created properly")
+    }
+
+    pub fn try_create_texture_svg(
+        &mut self,
+        file_path: &str,
+        position: Position,
+        scale_factor: f32,
+    ) -> Result<(Uuid, Rectangle), EngineError> {
         let texture_key = Uuid::new_v4();
-        let svg_texture = TextureSVG::new(
+        let texture = TextureSVG::new(
             texture_key,
             &self.device,
             &self.queue,
@@ -409,15 +1641,163 @@ impl<'a> PlutoniumEngine<'a> {
             &self.transform_bind_group_layout,
             position,
             scale_factor * self.dpi_scale_factor,
-        );
+        )?;
+        let dimensions = texture.dimensions() / self.dpi_scale_factor;
+
+        self.texture_map.insert(texture_key, texture);
+        Ok((texture_key, dimensions))
+    }
+
+    pub fn create_texture_svg_from_data(
+        &mut self,
+        svg_data: &str,
+        position: Position,
+        scale_factor: f32,
+    ) -> (Uuid, Rectangle) {
+        self.try_create_texture_svg_from_data(svg_data, position, scale_factor)
+            .expect("texture should always be created properly")
+    }
+
+    pub fn try_create_texture_svg_from_data(
+        &mut self,
+        svg_data: &str,
+        position: Position,
+        scale_factor: f32,
+    ) -> Result<(Uuid, Rectangle), EngineError> {
+        let texture_key = Uuid::new_v4();
+        let texture = TextureSVG::from_svg_data(
+            texture_key,
+            &self.device,
+            &self.queue,
+            svg_data,
+            &self.texture_bind_group_layout,
+            &self.transform_bind_group_layout,
+            position,
+            scale_factor * self.dpi_scale_factor,
+        )?;
+        let dimensions = texture.dimensions() / self.dpi_scale_factor;
+
+        self.texture_map.insert(texture_key, texture);
+        Ok((texture_key, dimensions))
+    }
+
+    /// Rasterizes the SVG at exactly `pixel_width` x `pixel_height` physical pixels, independent of
+    /// its declared size, so it can be drawn scaled up later without going blurry.
+    pub fn create_texture_svg_at_resolution(
+        &mut self,
+        file_path: &str,
+        position: Position,
+        pixel_width: u32,
+        pixel_height: u32,
+    ) -> (Uuid, Rectangle) {
+        self.try_create_texture_svg_at_resolution(file_path, position, pixel_width, pixel_height)
+            .expect("texture should always be created properly")
+    }
+
+    pub fn try_create_texture_svg_at_resolution(
+        &mut self,
+        file_path: &str,
+        position: Position,
+        pixel_width: u32,
+        pixel_height: u32,
+    ) -> Result<(Uuid, Rectangle), EngineError> {
+        let texture_key = Uuid::new_v4();
+        let svg_data = texture_svg::read_svg_file(file_path)?;
+        let texture = TextureSVG::from_svg_data_at_resolution(
+            texture_key,
+            &self.device,
+            &self.queue,
+            &svg_data,
+            &self.texture_bind_group_layout,
+            &self.transform_bind_group_layout,
+            position,
+            pixel_width,
+            pixel_height,
+        )?;
+        let dimensions = texture.dimensions() / self.dpi_scale_factor;
 
-        let texture = svg_texture.expect("texture should always be created properly");
+        self.texture_map.insert(texture_key, texture);
+        Ok((texture_key, dimensions))
+    }
+
+    /// Uploads pre-rasterized premultiplied pixels, e.g. from `texture_svg::rasterize_svg`.
+    pub fn create_texture_from_rgba(
+        &mut self,
+        image: &image::RgbaImage,
+        position: Position,
+    ) -> (Uuid, Rectangle) {
+        let texture_key = Uuid::new_v4();
+        let texture = TextureSVG::from_rgba(
+            texture_key,
+            &self.device,
+            &self.queue,
+            image,
+            &self.texture_bind_group_layout,
+            &self.transform_bind_group_layout,
+            position,
+        );
         let dimensions = texture.dimensions() / self.dpi_scale_factor;
 
         self.texture_map.insert(texture_key, texture);
         (texture_key, dimensions)
     }
 
+    /// Like `create_texture_svg_from_data`, but `currentColor` in the markup can be swapped later
+    /// with `set_svg_color`. Until then it renders black, the SVG default.
+    pub fn create_texture_svg_recolorable(
+        &mut self,
+        svg_data: &str,
+        position: Position,
+        scale_factor: f32,
+    ) -> (Uuid, Rectangle) {
+        self.try_create_texture_svg_recolorable(svg_data, position, scale_factor)
+            .expect("texture should always be created properly")
+    }
+
+    pub fn try_create_texture_svg_recolorable(
+        &mut self,
+        svg_data: &str,
+        position: Position,
+        scale_factor: f32,
+    ) -> Result<(Uuid, Rectangle), EngineError> {
+        let (texture_key, dimensions) =
+            self.try_create_texture_svg_from_data(svg_data, position, scale_factor)?;
+        self.svg_templates.insert(
+            texture_key,
+            SvgTemplate {
+                svg_data: svg_data.to_string(),
+                scale_factor,
+            },
+        );
+        Ok((texture_key, dimensions))
+    }
+
+    /// Re-rasterizes a recolorable SVG with `currentColor` replaced by `color` (RGBA, 0..=1).
+    ///
+    /// Returns false if `texture_key` wasn't created with `create_texture_svg_recolorable`.
+    pub fn set_svg_color(&mut self, texture_key: &Uuid, color: [f32; 4]) -> bool {
+        let (Some(template), Some(texture)) = (
+            self.svg_templates.get(texture_key),
+            self.texture_map.get_mut(texture_key),
+        ) else {
+            return false;
+        };
+        let [r, g, b, a] = color.map(|channel| (channel.clamp(0.0, 1.0) * 255.0).round() as u8);
+        let hex = format!("#{:02x}{:02x}{:02x}{:02x}", r, g, b, a);
+        let svg_data = template.svg_data.replace("currentColor", &hex);
+
+        texture
+            .replace_svg_data(
+                &self.device,
+                &self.queue,
+                &svg_data,
+                &self.texture_bind_group_layout,
+                template.scale_factor * self.dpi_scale_factor,
+            )
+            .is_ok()
+    }
+
+    /// Linearly filtered atlas; see `create_texture_atlas_with_filter`.
     pub fn create_texture_atlas(
         &mut self,
         svg_path: &str,
@@ -425,10 +1805,61 @@ impl<'a> PlutoniumEngine<'a> {
         scale_factor: f32,
         tile_size: Size,
     ) -> (Uuid, Rectangle) {
+        self.create_texture_atlas_with_filter(
+            svg_path,
+            position,
+            scale_factor,
+            tile_size,
+            wgpu::FilterMode::Linear,
+        )
+    }
+
+    /// `filter_mode` picks `Nearest` for crisp pixel art or `Linear` for smooth scaling.
+    pub fn create_texture_atlas_with_filter(
+        &mut self,
+        svg_path: &str,
+        position: Position,
+        scale_factor: f32,
+        tile_size: Size,
+        filter_mode: wgpu::FilterMode,
+    ) -> (Uuid, Rectangle) {
+        self.try_create_texture_atlas_with_filter(
+            svg_path,
+            position,
+            scale_factor,
+            tile_size,
+            filter_mode,
+        )
+        .expect("Failed to create texture atlas")
+    }
+
+    pub fn try_create_texture_atlas(
+        &mut self,
+        svg_path: &str,
+        position: Position,
+        scale_factor: f32,
+        tile_size: Size,
+    ) -> Result<(Uuid, Rectangle), EngineError> {
+        self.try_create_texture_atlas_with_filter(
+            svg_path,
+            position,
+            scale_factor,
+            tile_size,
+            wgpu::FilterMode::Linear,
+        )
+    }
+
+    pub fn try_create_texture_atlas_with_filter(
+        &mut self,
+        svg_path: &str,
+        position: Position,
+        scale_factor: f32,
+        tile_size: Size,
+        filter_mode: wgpu::FilterMode,
+    ) -> Result<(Uuid, Rectangle), EngineError> {
         let texture_key = Uuid::new_v4();
 
-        // Update to match new TextureAtlas interface
-        if let Some(atlas) = TextureAtlas::new(
+        let atlas = TextureAtlas::new(
             texture_key,
             &self.device,
             &self.queue,
@@ -438,17 +1869,80 @@ impl<'a> PlutoniumEngine<'a> {
             position,
             scale_factor * self.dpi_scale_factor, // Apply DPI scaling
             tile_size,
-        ) {
-            let dimensions = atlas.dimensions() / self.dpi_scale_factor;
+            filter_mode,
+        )?;
+        let dimensions = atlas.dimensions() / self.dpi_scale_factor;
 
-            let positioned_dimensions =
-                Rectangle::new(position.x, position.y, dimensions.width, dimensions.height);
+        let positioned_dimensions =
+            Rectangle::new(position.x, position.y, dimensions.width, dimensions.height);
 
-            self.atlas_map.insert(texture_key, atlas);
-            (texture_key, positioned_dimensions)
-        } else {
-            panic!("Failed to create texture atlas")
-        }
+        self.atlas_map.insert(texture_key, atlas);
+        Ok((texture_key, positioned_dimensions))
+    }
+
+    /// Like `try_create_texture_atlas`, but reuses the pixels in `cache_path` when it is newer than
+    /// the SVG and was saved for the same scale and tile size, rewriting the cache otherwise.
+    pub fn create_texture_atlas_cached(
+        &mut self,
+        svg_path: &str,
+        cache_path: &str,
+        position: Position,
+        scale_factor: f32,
+        tile_size: Size,
+        filter_mode: wgpu::FilterMode,
+    ) -> Result<(Uuid, Rectangle), EngineError> {
+        let texture_key = Uuid::new_v4();
+        let scale_factor = scale_factor * self.dpi_scale_factor;
+
+        let cached = texture_atlas::cache_is_fresh(cache_path, svg_path)
+            .then(|| {
+                TextureAtlas::load_cache(
+                    cache_path,
+                    texture_key,
+                    &self.device,
+                    &self.queue,
+                    &self.texture_bind_group_layout,
+                    &self.transform_bind_group_layout,
+                    position,
+                    scale_factor,
+                    tile_size,
+                    filter_mode,
+                )
+                .ok()
+            })
+            .flatten();
+        let atlas = match cached {
+            Some(atlas) => atlas,
+            None => {
+                let atlas = TextureAtlas::new(
+                    texture_key,
+                    &self.device,
+                    &self.queue,
+                    svg_path,
+                    &self.texture_bind_group_layout,
+                    &self.transform_bind_group_layout,
+                    position,
+                    scale_factor,
+                    tile_size,
+                    filter_mode,
+                )?;
+                if let Err(err) =
+                    atlas.save_cache(&self.device, &self.queue, cache_path, scale_factor)
+                {
+                    println!("Failed to write atlas cache: {}", err);
+                }
+                atlas
+            }
+        };
+        self.atlas_caches
+            .insert(texture_key, (cache_path.to_string(), scale_factor));
+        let dimensions = atlas.dimensions() / self.dpi_scale_factor;
+
+        let positioned_dimensions =
+            Rectangle::new(position.x, position.y, dimensions.width, dimensions.height);
+
+        self.atlas_map.insert(texture_key, atlas);
+        Ok((texture_key, positioned_dimensions))
     }
 
     pub fn create_font_texture_atlas(
@@ -497,12 +1991,13 @@ impl<'a> PlutoniumEngine<'a> {
 
         // Create texture view and sampler
         let texture_view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let filter_mode = wgpu::FilterMode::Linear;
         let sampler = self.device.create_sampler(&wgpu::SamplerDescriptor {
             address_mode_u: wgpu::AddressMode::ClampToEdge,
             address_mode_v: wgpu::AddressMode::ClampToEdge,
             address_mode_w: wgpu::AddressMode::ClampToEdge,
-            mag_filter: wgpu::FilterMode::Linear,
-            min_filter: wgpu::FilterMode::Linear,
+            mag_filter: filter_mode,
+            min_filter: filter_mode,
             mipmap_filter: wgpu::FilterMode::Linear,
             ..Default::default()
         });
@@ -524,7 +2019,7 @@ impl<'a> PlutoniumEngine<'a> {
         });
 
         // Create TextureAtlas and add it to the atlas_map
-        if let Some(atlas) = TextureAtlas::new_from_texture(
+        if let Some(mut atlas) = TextureAtlas::new_from_texture(
             atlas_id,
             texture,
             texture_bind_group,
@@ -535,32 +2030,166 @@ impl<'a> PlutoniumEngine<'a> {
             &self.queue,
             &self.transform_bind_group_layout,
             char_positions,
+            filter_mode,
         ) {
-            atlas
-                .save_debug_png(&self.device, &self.queue, "debug_atlas.png")
-                .unwrap();
+            atlas.retain_rgba(texture_data);
+            if let Some(path) = &self.debug_atlas_dump {
+                let path = path.to_string_lossy();
+                if let Err(err) = atlas.save_debug_png(&self.device, &self.queue, &path) {
+                    println!("Failed to dump glyph atlas to {}: {}", path, err);
+                }
+            }
             // Add to atlas_map
             self.atlas_map.insert(atlas_id, atlas);
 
-            // Create the internal representation
+            // Create the internal representation; glyph atlases are drawn through text objects, so
+            // they stay out of the object registry (picking, layout and auto-render)
             let internal = TextureAtlas2DInternal::new(
                 atlas_id,
                 atlas_id,
                 Rectangle::new(0.0, 0.0, width as f32, height as f32),
                 tile_size,
             );
-            let rc_internal = Rc::new(RefCell::new(internal));
-
-            self.pluto_objects.insert(atlas_id, rc_internal.clone());
-            self.update_queue.push(atlas_id);
 
-            TextureAtlas2D::new(rc_internal)
+            TextureAtlas2D::new(Rc::new(RefCell::new(internal)))
         } else {
             panic!("Failed to create font texture atlas");
         }
     }
     pub fn remove_object(&mut self, id: Uuid) {
         self.pluto_objects.remove(&id);
+        self.hidden_objects.remove(&id);
+    }
+
+    /// Shows or hides a registered object without unloading it; false if there's no such object.
+    pub fn set_visible(&mut self, id: &Uuid, visible: bool) -> bool {
+        let Some(object) = self.pluto_objects.get(id) else {
+            return false;
+        };
+        object.borrow_mut().set_visible(visible);
+        if visible {
+            self.hidden_objects.remove(id);
+        } else {
+            self.hidden_objects.insert(*id);
+        }
+        true
+    }
+
+    pub fn is_visible(&self, id: &Uuid) -> Option<bool> {
+        self.pluto_objects
+            .get(id)
+            .map(|object| !self.hidden_objects.contains(id) && object.borrow().is_visible())
+    }
+
+    /* OBJECT LOOKUP */
+    pub fn get_object(&self, id: &Uuid) -> Option<Rc<RefCell<dyn PlutoObject>>> {
+        self.pluto_objects.get(id).cloned()
+    }
+
+    /// Sampler filtering of an atlas, or `None` if there's no atlas under `atlas_key`.
+    pub fn atlas_filter_mode(&self, atlas_key: &Uuid) -> Option<wgpu::FilterMode> {
+        self.atlas_map.get(atlas_key).map(TextureAtlas::filter_mode)
+    }
+
+    pub fn atlas_grid_size(&self, atlas_key: &Uuid) -> Option<(usize, usize)> {
+        self.atlas_map.get(atlas_key).map(TextureAtlas::grid_size)
+    }
+
+    pub fn atlas_tile_count(&self, atlas_key: &Uuid) -> Option<usize> {
+        self.atlas_map.get(atlas_key).map(TextureAtlas::tile_count)
+    }
+
+    pub fn tile_index_from_rc(&self, atlas_key: &Uuid, row: usize, col: usize) -> Option<usize> {
+        self.atlas_map
+            .get(atlas_key)
+            .and_then(|atlas| atlas.tile_index_from_rc(row, col))
+    }
+
+    /// Names a tile so it can be found with `tile_index_by_name`; ignored for unknown atlases.
+    ///
+    /// Atlases from `create_texture_atlas_cached` rewrite their cache when a name is new or moved.
+    pub fn name_atlas_tile(&mut self, atlas_key: &Uuid, name: &str, tile_index: usize) {
+        let Some(atlas) = self.atlas_map.get_mut(atlas_key) else {
+            return;
+        };
+        if atlas.tile_index_by_name(name) == Some(tile_index) {
+            return;
+        }
+        atlas.name_tile(name, tile_index);
+        if let Some((cache_path, scale_factor)) = self.atlas_caches.get(atlas_key) {
+            if let Err(err) = atlas.save_cache(&self.device, &self.queue, cache_path, *scale_factor)
+            {
+                println!("Failed to write atlas cache: {}", err);
+            }
+        }
+    }
+
+    pub fn tile_index_by_name(&self, atlas_key: &Uuid, name: &str) -> Option<usize> {
+        self.atlas_map
+            .get(atlas_key)
+            .and_then(|atlas| atlas.tile_index_by_name(name))
+    }
+
+    /// Ids of all registered objects, in creation order.
+    pub fn object_ids(&self) -> Vec<Uuid> {
+        self.update_queue
+            .iter()
+            .filter(|id| self.pluto_objects.contains_key(id))
+            .copied()
+            .collect()
+    }
+
+    pub fn object_bounds(&self, id: &Uuid) -> Option<Rectangle> {
+        self.pluto_objects
+            .get(id)
+            .map(|object| object.borrow().dimensions())
+    }
+
+    /// `layout_hash` of every object's bounds in creation order.
+    pub fn layout_hash(&self) -> u64 {
+        let bounds: Vec<Rectangle> = self
+            .update_queue
+            .iter()
+            .filter_map(|id| self.object_bounds(id))
+            .collect();
+        layout_hash(&bounds)
+    }
+
+    /// Topmost visible object whose bounds contain `world_point`; later-created objects win overlaps.
+    pub fn object_at(&self, world_point: Position) -> Option<Uuid> {
+        self.update_queue.iter().rev().copied().find(|id| {
+            !self.hidden_objects.contains(id)
+                && self.pluto_objects.get(id).is_some_and(|object| {
+                    let object = object.borrow();
+                    object.is_visible() && object.dimensions().contains(world_point)
+                })
+        })
+    }
+
+    /// Converts a window position (e.g. the mouse) into world space through the letterbox and camera.
+    pub fn screen_to_world(&self, screen_point: Position) -> Position {
+        let screen_point = self.window_to_virtual(screen_point);
+        let camera_position = self.camera.get_pos(1.0);
+        Position {
+            x: screen_point.x + camera_position.x,
+            y: screen_point.y + camera_position.y,
+        }
+    }
+
+    /// Logical width of `text` laid out on one line in the given font.
+    pub fn measure_text(&self, text: &str, font_key: &str) -> f32 {
+        self.text_renderer
+            .measure_text(text, font_key, self.dpi_scale_factor)
+    }
+
+    pub fn measure_cache_hits(&self) -> u64 {
+        self.text_renderer.measure_cache_hits()
+    }
+
+    /// World-space rectangle currently covered by the viewport.
+    pub fn visible_world_rect(&self) -> Rectangle {
+        // viewport_size is already logical, so no dpi to divide out
+        self.camera.visible_world_rect(self.viewport_size, 1.0, 1.0)
     }
 
     /* OBJECT CREATION FUNCTIONS */
@@ -570,10 +2199,21 @@ impl<'a> PlutoniumEngine<'a> {
         position: Position,
         scale_factor: f32,
     ) -> Texture2D {
+        self.try_create_texture_2d(svg_path, position, scale_factor)
+            .expect("texture should always be created properly")
+    }
+
+    pub fn try_create_texture_2d(
+        &mut self,
+        svg_path: &str,
+        position: Position,
+        scale_factor: f32,
+    ) -> Result<Texture2D, EngineError> {
         let id = Uuid::new_v4();
 
         // Create the underlying texture
-        let (texture_key, dimensions) = self.create_texture_svg(svg_path, position, scale_factor);
+        let (texture_key, dimensions) =
+            self.try_create_texture_svg(svg_path, position, scale_factor)?;
 
         // Create the internal representation
         let internal = Texture2DInternal::new(id, texture_key, dimensions);
@@ -584,8 +2224,9 @@ impl<'a> PlutoniumEngine<'a> {
         self.update_queue.push(id);
 
         // Return the wrapper
-        Texture2D::new(rc_internal)
+        Ok(Texture2D::new(rc_internal))
     }
+    /// Like `try_create_text2d`, but panics if `font_key` hasn't been loaded.
     pub fn create_text2d(
         &mut self,
         text: &str,
@@ -593,14 +2234,24 @@ impl<'a> PlutoniumEngine<'a> {
         font_size: f32,
         position: Position,
     ) -> Text2D {
+        self.try_create_text2d(text, font_key, font_size, position)
+            .expect("Failed to load font")
+    }
+
+    pub fn try_create_text2d(
+        &mut self,
+        text: &str,
+        font_key: &str,
+        font_size: f32,
+        position: Position,
+    ) -> Result<Text2D, TextError> {
         let id = Uuid::new_v4();
-        // Ensure font is loaded, now with proper error handling
-        if !self.loaded_fonts.contains_key(font_key) {
-            panic!("Failed to load font");
-        }
+        self.ensure_font_loaded(font_key)?;
 
         // Create text dimensions based on measurement - now needs font_key
-        let width = self.text_renderer.measure_text(text, font_key);
+        let width = self
+            .text_renderer
+            .measure_text(text, font_key, self.dpi_scale_factor);
         let dimensions = Rectangle::new(position.x, position.y, width, font_size);
 
         let internal = Text2DInternal::new(
@@ -615,7 +2266,15 @@ impl<'a> PlutoniumEngine<'a> {
         self.pluto_objects.insert(id, rc_internal.clone());
         self.update_queue.push(id);
 
-        Text2D::new(rc_internal)
+        Ok(Text2D::new(rc_internal))
+    }
+
+    fn ensure_font_loaded(&self, font_key: &str) -> Result<(), TextError> {
+        if self.loaded_fonts.contains_key(font_key) {
+            Ok(())
+        } else {
+            Err(TextError::FontNotLoaded(font_key.to_string()))
+        }
     }
     pub fn create_texture_atlas_2d(
         &mut self,
@@ -623,12 +2282,34 @@ impl<'a> PlutoniumEngine<'a> {
         position: Position,
         scale_factor: f32,
         tile_size: Size,
+    ) -> TextureAtlas2D {
+        self.create_texture_atlas_2d_with_filter(
+            svg_path,
+            position,
+            scale_factor,
+            tile_size,
+            wgpu::FilterMode::Linear,
+        )
+    }
+
+    pub fn create_texture_atlas_2d_with_filter(
+        &mut self,
+        svg_path: &str,
+        position: Position,
+        scale_factor: f32,
+        tile_size: Size,
+        filter_mode: wgpu::FilterMode,
     ) -> TextureAtlas2D {
         let id = Uuid::new_v4();
 
         // Create texture atlas instead of regular texture
-        let (texture_key, dimensions) =
-            self.create_texture_atlas(svg_path, position, scale_factor, tile_size);
+        let (texture_key, dimensions) = self.create_texture_atlas_with_filter(
+            svg_path,
+            position,
+            scale_factor,
+            tile_size,
+            filter_mode,
+        );
 
         // Create the internal representation
         let internal = TextureAtlas2DInternal::new(id, texture_key, dimensions, tile_size);
@@ -651,7 +2332,32 @@ impl<'a> PlutoniumEngine<'a> {
         scale_factor: f32,
         callback: Option<Box<dyn Fn()>>,
     ) -> Button {
+        self.try_create_button(
+            svg_path,
+            text,
+            font_key,
+            font_size,
+            position,
+            scale_factor,
+            callback,
+        )
+        .expect("Failed to load font")
+    }
+
+    /// Creates a button, failing before anything is allocated if `font_key` isn't loaded.
+    #[allow(clippy::too_many_arguments)]
+    pub fn try_create_button(
+        &mut self,
+        svg_path: &str,
+        text: &str,
+        font_key: &str,
+        font_size: f32,
+        position: Position,
+        scale_factor: f32,
+        callback: Option<Box<dyn Fn()>>,
+    ) -> Result<Button, TextError> {
         let id = Uuid::new_v4();
+        self.ensure_font_loaded(font_key)?;
 
         // Create button texture
         let (button_texture_key, button_dimensions) =
@@ -662,7 +2368,8 @@ impl<'a> PlutoniumEngine<'a> {
             x: button_dimensions.x + (button_dimensions.width * 0.1),
             y: button_dimensions.y + (button_dimensions.height / 2.0),
         };
-        let text_object = self.create_text2d(text, font_key, font_size, text_position);
+        let text_object = self.try_create_text2d(text, font_key, font_size, text_position)?;
+        self.child_objects.insert(text_object.get_id());
 
         text_object.set_pos(Position { x: 0.0, y: 0.0 });
         // Create internal representation
@@ -680,7 +2387,7 @@ impl<'a> PlutoniumEngine<'a> {
         self.update_queue.push(id);
 
         // Return the wrapper
-        Button::new(rc_internal)
+        Ok(Button::new(rc_internal))
     }
 
     pub fn create_text_input(
@@ -691,10 +2398,22 @@ impl<'a> PlutoniumEngine<'a> {
         position: Position,
         scale_factor: f32,
     ) -> TextInput {
+        self.try_create_text_input(svg_path, font_key, font_size, position, scale_factor)
+            .expect("Failed to load font")
+    }
+
+    pub fn try_create_text_input(
+        &mut self,
+        svg_path: &str,
+        font_key: &str,
+        font_size: f32,
+        position: Position,
+        scale_factor: f32,
+    ) -> Result<TextInput, TextError> {
         let input_id = Uuid::new_v4();
 
         // Create button
-        let button = self.create_button(
+        let button = self.try_create_button(
             svg_path,
             "",
             font_key,
@@ -702,17 +2421,20 @@ impl<'a> PlutoniumEngine<'a> {
             position,
             scale_factor,
             None,
-        );
+        )?;
 
         // Create text object
         let text_position = Position {
             x: button.get_dimensions().x + (button.get_dimensions().width * 0.01),
             y: button.get_dimensions().y + (button.get_dimensions().height * 0.05),
         };
-        let text = self.create_text2d("", font_key, font_size, text_position);
+        let text = self.try_create_text2d("", font_key, font_size, text_position)?;
 
         // Create cursor
-        let cursor = self.create_text2d("|", font_key, font_size, position);
+        let cursor = self.try_create_text2d("|", font_key, font_size, position)?;
+
+        self.child_objects
+            .extend([button.get_id(), text.get_id(), cursor.get_id()]);
 
         // Create internal representation
         let dimensions = button.get_dimensions();
@@ -724,7 +2446,7 @@ impl<'a> PlutoniumEngine<'a> {
         self.update_queue.push(input_id);
 
         // Return the wrapper
-        TextInput::new(rc_internal)
+        Ok(TextInput::new(rc_internal))
     }
 
     pub fn new(
@@ -733,46 +2455,161 @@ impl<'a> PlutoniumEngine<'a> {
         size: PhysicalSize<u32>,
         dpi_scale_factor: f32,
     ) -> Self {
-        let adapter = block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
-            power_preference: wgpu::PowerPreference::default(),
-            force_fallback_adapter: false,
-            // Request an adapter which can render to our surface
-            compatible_surface: Some(&surface),
-        }))
-        .expect("Failed to find an appropriate adapter");
+        EngineBuilder::default().build(surface, instance, size, dpi_scale_factor)
+    }
+
+    /// Headless engine for unit tests, or None on machines without any GPU adapter.
+    #[cfg(test)]
+    pub(crate) fn for_tests(width: u32, height: u32) -> Option<Self> {
+        let instance = wgpu::Instance::default();
+        let adapter = block_on(instance.request_adapter(&wgpu::RequestAdapterOptions::default()))?;
+        Some(Self::from_adapter(
+            adapter,
+            None,
+            PhysicalSize::new(width, height),
+            1.0,
+            &EngineBuilder::default(),
+        ))
+    }
+
+    /// Builds an engine without a window; frames render into an offscreen texture read via `read_pixels`.
+    pub fn new_headless(
+        instance: wgpu::Instance,
+        size: PhysicalSize<u32>,
+        dpi_scale_factor: f32,
+    ) -> Self {
+        EngineBuilder::default().build_headless(instance, size, dpi_scale_factor)
+    }
+
+    pub(crate) fn from_adapter(
+        adapter: wgpu::Adapter,
+        surface: Option<wgpu::Surface<'a>>,
+        size: PhysicalSize<u32>,
+        dpi_scale_factor: f32,
+        settings: &EngineBuilder,
+    ) -> Self {
+        let (device, queue) = Self::request_device(&adapter).expect("Failed to create device");
+        Self::from_device(
+            Rc::new(adapter),
+            device,
+            queue,
+            surface,
+            size,
+            dpi_scale_factor,
+            settings,
+        )
+    }
 
-        // create the logical device and command queue
-        let (device, queue) = block_on(adapter.request_device(
+    /// Creates the logical device and command queue.
+    fn request_device(
+        adapter: &wgpu::Adapter,
+    ) -> Result<(wgpu::Device, wgpu::Queue), wgpu::RequestDeviceError> {
+        block_on(adapter.request_device(
             &wgpu::DeviceDescriptor {
                 label: None,
-                required_features: wgpu::Features::empty(),
+                // optional, only used by the wireframe debug mode
+                required_features: adapter.features() & wgpu::Features::POLYGON_MODE_LINE,
                 // Make sure we use the texture resolution limits from the adapter, so we can support images the size of the swapchain.
                 required_limits:
                     wgpu::Limits::downlevel_webgl2_defaults().using_resolution(adapter.limits()),
             },
             None,
         ))
-        .expect("Failed to create device");
+    }
+
+    /// Builds the surface setup, pipelines and an empty scene around a freshly requested device.
+    fn from_device(
+        adapter: Rc<wgpu::Adapter>,
+        device: wgpu::Device,
+        queue: wgpu::Queue,
+        surface: Option<wgpu::Surface<'a>>,
+        size: PhysicalSize<u32>,
+        dpi_scale_factor: f32,
+        settings: &EngineBuilder,
+    ) -> Self {
+        let device_lost = Arc::new(Mutex::new(None));
+        let lost_reason = Arc::clone(&device_lost);
+        device.set_device_lost_callback(move |reason, message| {
+            // dropping the engine (or replacing its device) also "loses" it; only record real losses
+            if reason != wgpu::DeviceLostReason::Dropped {
+                *lost_reason
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner()) =
+                    Some(format!("{:?}: {}", reason, message));
+            }
+        });
+
+        // headless targets can use any format; keep BGRA so readback matches a typical swapchain
+        let mut format = wgpu::TextureFormat::Bgra8UnormSrgb;
+        let mut present_mode = settings.present_mode;
+        if let Some(surface) = &surface {
+            let capabilities = surface.get_capabilities(&adapter);
+            // the shader outputs linear color, so prefer an sRGB format the surface actually offers
+            let negotiated = capabilities
+                .formats
+                .iter()
+                .copied()
+                .find(wgpu::TextureFormat::is_srgb)
+                .or_else(|| capabilities.formats.first().copied())
+                .unwrap_or(format);
+            format = match settings.surface_format {
+                Some(requested) if capabilities.formats.contains(&requested) => requested,
+                Some(requested) => {
+                    println!(
+                        "Surface format {:?} unsupported, using {:?}",
+                        requested, negotiated
+                    );
+                    negotiated
+                }
+                None => negotiated,
+            };
+            if !capabilities.present_modes.contains(&present_mode) {
+                println!("Present mode {:?} unsupported, using Fifo", present_mode);
+                present_mode = wgpu::PresentMode::Fifo; // always supported, V-Sync
+            }
+        } else if let Some(requested) = settings.surface_format {
+            format = requested;
+        }
+        let sample_count = if adapter
+            .get_texture_format_features(format)
+            .flags
+            .sample_count_supported(settings.sample_count)
+        {
+            settings.sample_count
+        } else {
+            println!(
+                "MSAA x{} unsupported, rendering without it",
+                settings.sample_count
+            );
+            1
+        };
 
         let config = wgpu::SurfaceConfiguration {
             desired_maximum_frame_latency: 2,
             alpha_mode: wgpu::CompositeAlphaMode::Auto,
-            view_formats: vec![wgpu::TextureFormat::Bgra8UnormSrgb],
+            view_formats: vec![format],
             usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
-            format: wgpu::TextureFormat::Bgra8UnormSrgb, // Assume `surface` and `adapter` are already defined
-            width: size.width,                           // Set to your window's initial width
-            height: size.height,                         // Set to your window's initial height
-            present_mode: wgpu::PresentMode::Fifo,       // This enables V-Sync
+            format,
+            width: size.width,   // Set to your window's initial width
+            height: size.height, // Set to your window's initial height
+            present_mode,
         };
 
-        surface.configure(&device, &config);
+        let offscreen_target = match &surface {
+            Some(surface) => {
+                surface.configure(&device, &config);
+                None
+            }
+            None => Some(Self::create_offscreen_target(&device, &config)),
+        };
+        let msaa_target = Self::create_msaa_target(&device, &config, sample_count);
 
         let transform_bind_group_layout =
             device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
                 label: Some("transform_bind_group_layout"),
                 entries: &[wgpu::BindGroupLayoutEntry {
                     binding: 0,
-                    visibility: wgpu::ShaderStages::VERTEX, // Transformation matrix is used in the vertex shader
+                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT, // matrix in the vertex shader, opacity in the fragment shader
                     ty: wgpu::BindingType::Buffer {
                         ty: wgpu::BufferBindingType::Uniform,
                         has_dynamic_offset: false,
@@ -842,34 +2679,192 @@ impl<'a> PlutoniumEngine<'a> {
             push_constant_ranges: &[],
         });
 
-        // set up render pipeline
+        // set up render pipelines; the debug variants differ only in fragment entry, blend and fill
+        let create_pipeline =
+            |fragment_entry: &str, blend: wgpu::BlendState, polygon_mode: wgpu::PolygonMode| {
+                device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                    label: None,
+                    layout: Some(&pipeline_layout),
+                    vertex: wgpu::VertexState {
+                        module: &shader,
+                        entry_point: "vs_main",
+                        buffers: &[wgpu::VertexBufferLayout {
+                            array_stride: std::mem::size_of::<Vertex>() as wgpu::BufferAddress,
+                            step_mode: wgpu::VertexStepMode::Vertex,
+                            attributes: &wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x2],
+                        }],
+                        compilation_options: wgpu::PipelineCompilationOptions::default(),
+                    },
+                    fragment: Some(wgpu::FragmentState {
+                        module: &shader,
+                        entry_point: fragment_entry,
+                        targets: &[Some(wgpu::ColorTargetState {
+                            format: config.format,
+                            blend: Some(blend),
+                            write_mask: wgpu::ColorWrites::ALL,
+                        })],
+                        compilation_options: wgpu::PipelineCompilationOptions::default(),
+                    }),
+                    primitive: wgpu::PrimitiveState {
+                        polygon_mode,
+                        ..Default::default()
+                    },
+                    depth_stencil: None,
+                    multisample: wgpu::MultisampleState {
+                        count: sample_count,
+                        ..Default::default()
+                    },
+                    multiview: None,
+                })
+            };
+        let render_pipeline = create_pipeline(
+            "fs_main",
+            wgpu::BlendState::ALPHA_BLENDING,
+            wgpu::PolygonMode::Fill,
+        );
+        let additive = wgpu::BlendComponent {
+            src_factor: wgpu::BlendFactor::One,
+            dst_factor: wgpu::BlendFactor::One,
+            operation: wgpu::BlendOperation::Add,
+        };
+        let overdraw_pipeline = create_pipeline(
+            "fs_overdraw",
+            wgpu::BlendState {
+                color: additive,
+                alpha: additive,
+            },
+            wgpu::PolygonMode::Fill,
+        );
+        let wireframe_pipeline = device
+            .features()
+            .contains(wgpu::Features::POLYGON_MODE_LINE)
+            .then(|| {
+                create_pipeline(
+                    "fs_main",
+                    wgpu::BlendState::ALPHA_BLENDING,
+                    wgpu::PolygonMode::Line,
+                )
+            });
 
-        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: None,
-            layout: Some(&pipeline_layout),
+        let instanced_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Instanced Pipeline Layout"),
+                bind_group_layouts: &[&texture_bind_group_layout],
+                push_constant_ranges: &[],
+            });
+        // the instanced variants mirror the ones above, so debug modes cover instanced draws too
+        let create_instanced_pipeline =
+            |fragment_entry: &str, blend: wgpu::BlendState, polygon_mode: wgpu::PolygonMode| {
+                device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                    label: Some("Instanced Pipeline"),
+                    layout: Some(&instanced_pipeline_layout),
+                    vertex: wgpu::VertexState {
+                        module: &shader,
+                        entry_point: "vs_instanced",
+                        buffers: &[
+                            wgpu::VertexBufferLayout {
+                                array_stride: std::mem::size_of::<Vertex>() as wgpu::BufferAddress,
+                                step_mode: wgpu::VertexStepMode::Vertex,
+                                attributes: &wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x2],
+                            },
+                            wgpu::VertexBufferLayout {
+                                array_stride: std::mem::size_of::<SpriteInstance>() as wgpu::BufferAddress,
+                                step_mode: wgpu::VertexStepMode::Instance,
+                                attributes: &wgpu::vertex_attr_array![
+                                    2 => Float32x4,
+                                    3 => Float32x4,
+                                    4 => Float32x4,
+                                    5 => Float32x4,
+                                    6 => Float32,
+                                    7 => Float32x2,
+                                    8 => Float32x2,
+                                    9 => Float32x4,
+                                ],
+                            },
+                        ],
+                        compilation_options: wgpu::PipelineCompilationOptions::default(),
+                    },
+                    fragment: Some(wgpu::FragmentState {
+                        module: &shader,
+                        entry_point: fragment_entry,
+                        targets: &[Some(wgpu::ColorTargetState {
+                            format: config.format,
+                            blend: Some(blend),
+                            write_mask: wgpu::ColorWrites::ALL,
+                        })],
+                        compilation_options: wgpu::PipelineCompilationOptions::default(),
+                    }),
+                    primitive: wgpu::PrimitiveState {
+                        polygon_mode,
+                        ..Default::default()
+                    },
+                    depth_stencil: None,
+                    multisample: wgpu::MultisampleState {
+                        count: sample_count,
+                        ..Default::default()
+                    },
+                    multiview: None,
+                })
+            };
+        let instanced_pipeline = create_instanced_pipeline(
+            "fs_instanced",
+            wgpu::BlendState::ALPHA_BLENDING,
+            wgpu::PolygonMode::Fill,
+        );
+        let instanced_overdraw_pipeline = create_instanced_pipeline(
+            "fs_instanced_overdraw",
+            wgpu::BlendState {
+                color: additive,
+                alpha: additive,
+            },
+            wgpu::PolygonMode::Fill,
+        );
+        let instanced_wireframe_pipeline = wireframe_pipeline.is_some().then(|| {
+            create_instanced_pipeline(
+                "fs_instanced",
+                wgpu::BlendState::ALPHA_BLENDING,
+                wgpu::PolygonMode::Line,
+            )
+        });
+
+        let fill_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Fill Pipeline Layout"),
+            bind_group_layouts: &[],
+            push_constant_ranges: &[],
+        });
+        let constant = wgpu::BlendComponent {
+            src_factor: wgpu::BlendFactor::Constant,
+            dst_factor: wgpu::BlendFactor::Zero,
+            operation: wgpu::BlendOperation::Add,
+        };
+        let fill_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Fill Pipeline"),
+            layout: Some(&fill_pipeline_layout),
             vertex: wgpu::VertexState {
                 module: &shader,
-                entry_point: "vs_main",
-                buffers: &[wgpu::VertexBufferLayout {
-                    array_stride: std::mem::size_of::<Vertex>() as wgpu::BufferAddress,
-                    step_mode: wgpu::VertexStepMode::Vertex,
-                    attributes: &wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x2],
-                }],
+                entry_point: "vs_fill",
+                buffers: &[],
                 compilation_options: wgpu::PipelineCompilationOptions::default(),
             },
             fragment: Some(wgpu::FragmentState {
                 module: &shader,
-                entry_point: "fs_main",
+                entry_point: "fs_fill",
                 targets: &[Some(wgpu::ColorTargetState {
-                    format: wgpu::TextureFormat::Bgra8UnormSrgb,
-                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    format: config.format,
+                    blend: Some(wgpu::BlendState {
+                        color: constant,
+                        alpha: constant,
+                    }),
                     write_mask: wgpu::ColorWrites::ALL,
                 })],
                 compilation_options: wgpu::PipelineCompilationOptions::default(),
             }),
             primitive: wgpu::PrimitiveState::default(),
             depth_stencil: None,
-            multisample: wgpu::MultisampleState::default(),
+            multisample: wgpu::MultisampleState {
+                count: sample_count,
+                ..Default::default()
+            },
             multiview: None,
         });
 
@@ -890,6 +2885,7 @@ impl<'a> PlutoniumEngine<'a> {
         Self {
             size,
             surface,
+            offscreen_target,
             device,
             dpi_scale_factor,
             queue,
@@ -897,6 +2893,7 @@ impl<'a> PlutoniumEngine<'a> {
             render_pipeline,
             texture_bind_group_layout,
             transform_bind_group_layout,
+            uv_bind_group_layout,
             texture_map,
             atlas_map,
             pluto_objects,
@@ -906,6 +2903,704 @@ impl<'a> PlutoniumEngine<'a> {
             camera,
             text_renderer,
             loaded_fonts,
+            pixel_snapping: false,
+            virtual_resolution: None,
+            min_size: PhysicalSize::new(1, 1),
+            hovered_cursor_icon: CursorIcon::Default,
+            cursor_icon_override: None,
+            cursor_visible: true,
+            svg_templates: HashMap::new(),
+            static_batches: HashMap::new(),
+            device_lost,
+            adapter_info: adapter.get_info(),
+            #[cfg(feature = "retain-cpu-textures")]
+            adapter,
+            #[cfg(feature = "retain-cpu-textures")]
+            settings: settings.clone(),
+            debug_atlas_dump: None,
+            clear_color: settings.clear_color,
+            sample_count,
+            msaa_target,
+            debug_mode: DebugMode::Normal,
+            overdraw_pipeline,
+            wireframe_pipeline,
+            instanced_pipeline,
+            instanced_overdraw_pipeline,
+            instanced_wireframe_pipeline,
+            fill_pipeline,
+            frame_count: 0,
+            stats_reporter: None,
+            cpu_update_time: Duration::ZERO,
+            cpu_render_time: Duration::ZERO,
+            auto_render: false,
+            debug_draw: DebugDraw::default(),
+            atlas_batching: false,
+            atlas_batches: Vec::new(),
+            child_objects: HashSet::new(),
+            hidden_objects: HashSet::new(),
+            atlas_caches: HashMap::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SQUARE_SVG: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/media/square.svg");
+    const ROBOTO: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/media/roboto.ttf");
+
+    fn square_atlas(engine: &mut PlutoniumEngine) -> Uuid {
+        let tile_size = Size {
+            width: 50.0,
+            height: 50.0,
+        };
+        let (atlas_id, _) = engine.create_texture_atlas_with_filter(
+            SQUARE_SVG,
+            Position::default(),
+            1.0,
+            tile_size,
+            wgpu::FilterMode::Nearest,
+        );
+        atlas_id
+    }
+
+    #[test]
+    fn atlas_batching_only_merges_back_to_back_tiles() {
+        let Some(mut engine) = PlutoniumEngine::for_tests(64, 64) else {
+            return;
+        };
+        let atlas_id = square_atlas(&mut engine);
+        let (texture_key, _) = engine.create_texture_svg(SQUARE_SVG, Position::default(), 1.0);
+        engine.set_atlas_batching(true);
+
+        engine.queue_tile(&atlas_id, 0, Position::default());
+        engine.queue_tile(&atlas_id, 1, Position::default());
+        engine.queue_texture(&texture_key, None);
+        engine.queue_tile(&atlas_id, 2, Position::default());
+
+        assert_eq!(engine.render_queue.len(), 3);
+        assert_eq!(engine.atlas_batches.len(), 2);
+        assert_eq!(engine.atlas_batches[0].instances.len(), 2);
+        assert_eq!(engine.atlas_batches[1].instances.len(), 1);
+
+        // instanced draws go through the debug pipelines too
+        engine.set_debug_mode(DebugMode::Overdraw);
+        engine.render().unwrap();
+    }
+
+    /// Keeps no visibility flag, relying on the trait's defaults.
+    struct Marker {
+        id: Uuid,
+        texture_key: Uuid,
+        dimensions: Rectangle,
+    }
+
+    impl PlutoObject for Marker {
+        fn texture_key(&self) -> Uuid {
+            self.texture_key
+        }
+        fn get_id(&self) -> Uuid {
+            self.id
+        }
+        fn dimensions(&self) -> Rectangle {
+            self.dimensions
+        }
+        fn pos(&self) -> Position {
+            self.dimensions.pos()
+        }
+        fn set_dimensions(&mut self, new_dimensions: Rectangle) {
+            self.dimensions = new_dimensions;
+        }
+        fn set_pos(&mut self, new_pos: Position) {
+            self.dimensions.set_pos(new_pos);
+        }
+    }
+
+    #[test]
+    fn invisible_objects_are_skipped_but_stay_registered() {
+        let Some(mut engine) = PlutoniumEngine::for_tests(64, 64) else {
+            return;
+        };
+        engine.set_auto_render(true);
+        let sprite = engine.create_texture_2d(SQUARE_SVG, Position::default(), 1.0);
+        let texture_key = engine.pluto_objects[&sprite.get_id()]
+            .borrow()
+            .texture_key();
+        let marker_id = Uuid::new_v4();
+        let marker = Marker {
+            id: marker_id,
+            texture_key,
+            dimensions: Rectangle::new(0.0, 0.0, 10.0, 10.0),
+        };
+        engine
+            .pluto_objects
+            .insert(marker_id, Rc::new(RefCell::new(marker)));
+        engine.update_queue.push(marker_id);
+        assert_eq!(engine.queue_auto_render(), 2);
+        engine.clear_render_queue();
+
+        assert!(engine.set_visible(&sprite.get_id(), false));
+        assert!(engine.set_visible(&marker_id, false));
+        assert_eq!(engine.queue_auto_render(), 0);
+        assert!(engine.get_object(&sprite.get_id()).is_some());
+        assert_eq!(engine.is_visible(&sprite.get_id()), Some(false));
+        assert_eq!(engine.is_visible(&marker_id), Some(false));
+        assert_eq!(engine.object_at(Position { x: 5.0, y: 5.0 }), None);
+
+        assert!(engine.set_visible(&marker_id, true));
+        assert_eq!(engine.queue_auto_render(), 1);
+    }
+
+    #[test]
+    fn auto_render_queues_visible_texture2ds() {
+        let Some(mut engine) = PlutoniumEngine::for_tests(64, 64) else {
+            return;
+        };
+        engine.set_auto_render(true);
+        let shown = engine.create_texture_2d(SQUARE_SVG, Position::default(), 1.0);
+        let hidden = engine.create_texture_2d(SQUARE_SVG, Position::default(), 1.0);
+        let hidden_by_id = engine.create_texture_2d(SQUARE_SVG, Position::default(), 1.0);
+        hidden.set_visible(false);
+        assert!(engine.set_visible(&hidden_by_id.get_id(), false));
+
+        let shown_key = engine.pluto_objects[&shown.get_id()].borrow().texture_key();
+        assert_eq!(engine.queue_auto_render(), 1);
+        assert!(matches!(
+            engine.render_queue[..],
+            [RenderItem::Texture { texture_key, .. }] if texture_key == shown_key
+        ));
+    }
+
+    #[test]
+    fn resize_clamps_to_the_min_size() {
+        let Some(mut engine) = PlutoniumEngine::for_tests(64, 64) else {
+            return;
+        };
+        engine.set_min_size(PhysicalSize::new(48, 40));
+        engine.resize(&PhysicalSize::new(16, 100), 1.0);
+        assert_eq!((engine.config.width, engine.config.height), (48, 100));
+        assert_eq!(engine.size, PhysicalSize::new(48, 100));
+
+        // frames still render at the clamped size
+        engine.render().unwrap();
+        assert_eq!(engine.read_pixels().unwrap().len(), 48 * 100 * 4);
+    }
+
+    #[test]
+    fn get_object_returns_registered_objects() {
+        let Some(mut engine) = PlutoniumEngine::for_tests(64, 64) else {
+            return;
+        };
+        let sprite = engine.create_texture_2d(SQUARE_SVG, Position { x: 10.0, y: 20.0 }, 1.0);
+
+        let object = engine.get_object(&sprite.get_id()).unwrap();
+        let dimensions = object.borrow().dimensions();
+        assert_eq!(object.borrow().get_id(), sprite.get_id());
+        assert_eq!(
+            (
+                dimensions.x,
+                dimensions.y,
+                dimensions.width,
+                dimensions.height
+            ),
+            (10.0, 20.0, 100.0, 100.0)
+        );
+        assert!(engine.get_object(&Uuid::new_v4()).is_none());
+    }
+
+    #[test]
+    fn object_at_picks_the_top_object() {
+        let Some(mut engine) = PlutoniumEngine::for_tests(64, 64) else {
+            return;
+        };
+        // the glyph atlas also starts at the origin but isn't an object
+        assert!(engine.load_font(ROBOTO, 16.0, "roboto").is_ok());
+        // square.svg is 100x100
+        let bottom = engine.create_texture_2d(SQUARE_SVG, Position::default(), 1.0);
+        let top = engine.create_texture_2d(SQUARE_SVG, Position { x: 50.0, y: 50.0 }, 1.0);
+
+        assert_eq!(
+            engine.object_at(Position { x: 75.0, y: 75.0 }),
+            Some(top.get_id())
+        );
+        assert_eq!(
+            engine.object_at(Position { x: 25.0, y: 25.0 }),
+            Some(bottom.get_id())
+        );
+        assert_eq!(engine.object_at(Position { x: 175.0, y: 25.0 }), None);
+
+        // re-installed glyph atlases don't end up on top either
+        assert!(engine.set_dpi_scale_factor(2.0).is_ok());
+        assert_eq!(
+            engine.object_at(Position { x: 25.0, y: 25.0 }),
+            Some(bottom.get_id())
+        );
+    }
+
+    #[test]
+    fn layout_hash_ignores_fonts_and_dpi() {
+        let Some(mut engine) = PlutoniumEngine::for_tests(64, 64) else {
+            return;
+        };
+        let sprite = engine.create_texture_2d(SQUARE_SVG, Position::default(), 1.0);
+        let hash = engine.layout_hash();
+
+        assert!(engine.load_font(ROBOTO, 16.0, "roboto").is_ok());
+        assert_eq!(engine.layout_hash(), hash);
+        assert!(engine.set_dpi_scale_factor(2.0).is_ok());
+        assert_eq!(engine.layout_hash(), hash);
+        assert_eq!(engine.object_ids(), vec![sprite.get_id()]);
+    }
+
+    #[test]
+    fn static_batches_snap_when_drawn() {
+        let Some(mut engine) = PlutoniumEngine::for_tests(64, 64) else {
+            return;
+        };
+        let atlas_id = square_atlas(&mut engine);
+        let batch =
+            engine.build_static_atlas_batch(&atlas_id, &[(0, Position { x: 10.4, y: 0.0 })]);
+        engine.draw_static_batch(&batch);
+        engine.draw_static_batch(&batch);
+        assert_eq!(engine.static_batch_uploads(&batch), Some(1));
+
+        // snapping turned on after the batch was built still applies to it
+        engine.set_pixel_snapping(true);
+        engine.draw_static_batch(&batch);
+        assert_eq!(engine.static_batch_uploads(&batch), Some(2));
+    }
+
+    #[test]
+    fn frame_stats_count_issued_draw_calls() {
+        let Some(mut engine) = PlutoniumEngine::for_tests(64, 64) else {
+            return;
+        };
+        let draw_calls = Rc::new(std::cell::Cell::new(usize::MAX));
+        let reported = Rc::clone(&draw_calls);
+        engine.set_stats_reporter(1, move |stats| reported.set(stats.draw_calls));
+        let atlas_id = square_atlas(&mut engine);
+        let tiles = [(0, Position::default()); 3];
+        let batch = engine.build_static_atlas_batch(&atlas_id, &tiles);
+
+        engine.draw_static_batch(&batch);
+        engine.queue_tile(&atlas_id, 0, Position::default());
+        engine.render().unwrap();
+        // the static batch is one instanced draw however many tiles it holds
+        assert_eq!(draw_calls.get(), 2);
+
+        // a texture removed after queuing is skipped, not drawn
+        let (texture_key, _) = engine.create_texture_svg(SQUARE_SVG, Position::default(), 1.0);
+        engine.clear_render_queue();
+        engine.queue_texture(&texture_key, None);
+        engine.texture_map.remove(&texture_key);
+        engine.render().unwrap();
+        assert_eq!(draw_calls.get(), 0);
+    }
+
+    #[test]
+    fn sprite_instances_are_one_draw_call() {
+        let Some(mut engine) = PlutoniumEngine::for_tests(64, 64) else {
+            return;
+        };
+        let draw_calls = Rc::new(std::cell::Cell::new(usize::MAX));
+        let reported = Rc::clone(&draw_calls);
+        engine.set_stats_reporter(1, move |stats| reported.set(stats.draw_calls));
+        let (texture_key, _) = engine.create_texture_svg(SQUARE_SVG, Position::default(), 0.1);
+        let transforms: Vec<(Position, DrawParams)> = (0..5000)
+            .map(|i| {
+                let position = Position {
+                    x: (i % 64) as f32,
+                    y: (i / 64 % 64) as f32,
+                };
+                (position, DrawParams::default())
+            })
+            .collect();
+
+        engine.draw_sprite_instances(&texture_key, &transforms);
+        assert_eq!(engine.render_queue.len(), 1);
+        engine.render().unwrap();
+        assert_eq!(draw_calls.get(), 1);
+    }
+
+    #[test]
+    fn batches_grow_the_render_queue_once() {
+        let Some(mut engine) = PlutoniumEngine::for_tests(64, 64) else {
+            return;
+        };
+        let (texture_key, _) = engine.create_texture_svg(SQUARE_SVG, Position::default(), 0.1);
+        let sprites: Vec<(Uuid, Position, DrawParams)> = (0..10_000)
+            .map(|i| {
+                let position = Position {
+                    x: (i % 64) as f32,
+                    y: 0.0,
+                };
+                (texture_key, position, DrawParams::default())
+            })
+            .collect();
+        engine.clear_render_queue();
+        engine.render_queue.shrink_to_fit();
+
+        engine.draw_sprites_batch(&sprites);
+        assert_eq!(engine.render_queue.len(), 10_000);
+        assert_eq!(engine.render_queue.capacity(), 10_000);
+
+        let atlas_id = square_atlas(&mut engine);
+        let tiles: Vec<(Uuid, usize, Position, DrawParams)> = sprites
+            .iter()
+            .map(|(_, position, params)| (atlas_id, 0, *position, *params))
+            .collect();
+        engine.clear_render_queue();
+        engine.render_queue.shrink_to_fit();
+
+        engine.draw_tiles_batch(&tiles);
+        assert_eq!(engine.render_queue.len(), 10_000);
+        assert_eq!(engine.render_queue.capacity(), 10_000);
+    }
+
+    #[test]
+    fn headless_frames_read_back_the_clear_color() {
+        let Some(mut engine) = PlutoniumEngine::for_tests(8, 4) else {
+            return;
+        };
+        engine.set_clear_color([1.0, 0.0, 0.0, 1.0]);
+        engine.render().unwrap();
+        let pixels = engine.read_pixels().unwrap();
+        assert_eq!(pixels.len(), 8 * 4 * 4);
+        assert!(pixels.chunks(4).all(|pixel| pixel == [255, 0, 0, 255]));
+
+        // a zero-sized engine still has a 1x1 target to read
+        let Some(mut engine) = PlutoniumEngine::for_tests(0, 0) else {
+            return;
+        };
+        engine.render().unwrap();
+        assert_eq!(engine.read_pixels().unwrap().len(), 4);
+    }
+
+    #[test]
+    fn builder_settings_reach_the_engine() {
+        let instance = wgpu::Instance::default();
+        let power_preference = wgpu::PowerPreference::LowPower;
+        let Some(expected) = block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference,
+            ..Default::default()
+        })) else {
+            return;
+        };
+        let mut engine = EngineBuilder::new()
+            .power_preference(power_preference)
+            .clear_color([0.0, 1.0, 0.0, 1.0])
+            .build_headless(instance, PhysicalSize::new(4, 4), 1.0);
+        assert_eq!(engine.adapter_info(), &expected.get_info());
+
+        engine.render().unwrap();
+        let pixels = engine.read_pixels().unwrap();
+        assert!(pixels.chunks(4).all(|pixel| pixel == [0, 255, 0, 255]));
+    }
+
+    const WHITE_SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="16" height="16"><rect width="16" height="16" fill="white"/></svg>"#;
+
+    #[test]
+    fn opacity_scales_the_drawn_alpha() {
+        let Some(mut engine) = PlutoniumEngine::for_tests(16, 16) else {
+            return;
+        };
+        engine.set_clear_color([0.0, 0.0, 0.0, 0.0]);
+        let (texture_key, _) =
+            engine.create_texture_svg_from_data(WHITE_SVG, Position::default(), 1.0);
+        let params = DrawParams {
+            opacity: 0.5,
+            ..Default::default()
+        };
+        engine.queue_texture_with_params(&texture_key, Some(Position::default()), params);
+        engine.render().unwrap();
+
+        let pixels = engine.read_pixels().unwrap();
+        let center = (8 * 16 + 8) * 4;
+        let alpha = pixels[center + 3];
+        assert!((126..=130).contains(&alpha), "alpha {alpha}");
+    }
+
+    #[test]
+    fn repeat_address_mode_wraps_scrolled_textures() {
+        let Some(mut engine) = PlutoniumEngine::for_tests(16, 16) else {
+            return;
+        };
+        let halves = r#"<svg xmlns="http://www.w3.org/2000/svg" width="16" height="16"><rect width="8" height="16" fill="red"/><rect x="8" width="8" height="16" fill="blue"/></svg>"#;
+        let (texture_key, _) =
+            engine.create_texture_svg_from_data(halves, Position::default(), 1.0);
+        // sizes the quad to the viewport
+        engine.update(None, &None);
+        let right_pixel_after_scrolling = |engine: &mut PlutoniumEngine| {
+            engine.clear_render_queue();
+            engine.queue_texture_scrolled(
+                &texture_key,
+                Position::default(),
+                [0.5, 0.0],
+                [1.0, 1.0],
+            );
+            engine.render().unwrap();
+            let pixels = engine.read_pixels().unwrap();
+            let right = (8 * 16 + 14) * 4;
+            [pixels[right], pixels[right + 1], pixels[right + 2]]
+        };
+
+        // clamped, the right quarter stretches the texture's blue edge
+        assert_eq!(right_pixel_after_scrolling(&mut engine), [0, 0, 255]);
+        assert!(engine.set_texture_address_mode(&texture_key, AddressMode::Repeat));
+        // repeating, UVs past 1 wrap back around to the red left half
+        assert_eq!(right_pixel_after_scrolling(&mut engine), [255, 0, 0]);
+    }
+
+    #[test]
+    fn overdraw_mode_brightens_overlapping_draws() {
+        let Some(mut engine) = PlutoniumEngine::for_tests(32, 16) else {
+            return;
+        };
+        assert!(engine.set_debug_mode(DebugMode::Overdraw));
+        let (texture_key, _) =
+            engine.create_texture_svg_from_data(WHITE_SVG, Position::default(), 1.0);
+        engine.update(None, &None);
+        // the second copy covers only the middle third
+        engine.queue_texture(&texture_key, Some(Position::default()));
+        engine.queue_texture(&texture_key, Some(Position { x: 8.0, y: 0.0 }));
+        engine.render().unwrap();
+
+        let pixels = engine.read_pixels().unwrap();
+        let red_at = |x: usize| pixels[(8 * 32 + x) * 4];
+        let (uncovered, once, twice) = (red_at(28), red_at(4), red_at(12));
+        assert_eq!(uncovered, 0);
+        assert!(once > uncovered, "{once}");
+        assert!(twice > once, "{once} vs {twice}");
+    }
+
+    fn mouse_at(x: f32, y: f32) -> MouseInfo {
+        MouseInfo {
+            is_rmb_clicked: false,
+            is_lmb_clicked: false,
+            is_mmb_clicked: false,
+            mouse_pos: Position { x, y },
         }
     }
+
+    #[test]
+    fn hovered_widgets_pick_the_cursor() {
+        let Some(mut engine) = PlutoniumEngine::for_tests(400, 400) else {
+            return;
+        };
+        assert!(engine.load_font(ROBOTO, 16.0, "roboto").is_ok());
+        // square.svg is 100x100
+        engine.create_button(
+            SQUARE_SVG,
+            "OK",
+            "roboto",
+            16.0,
+            Position { x: 0.0, y: 0.0 },
+            1.0,
+            None,
+        );
+        engine.create_text_input(
+            SQUARE_SVG,
+            "roboto",
+            16.0,
+            Position { x: 200.0, y: 0.0 },
+            1.0,
+        );
+
+        engine.update(Some(mouse_at(50.0, 50.0)), &None);
+        assert_eq!(engine.cursor_icon(), Some(CursorIcon::Pointer));
+        engine.update(Some(mouse_at(250.0, 50.0)), &None);
+        assert_eq!(engine.cursor_icon(), Some(CursorIcon::Text));
+        engine.update(Some(mouse_at(150.0, 300.0)), &None);
+        assert_eq!(engine.cursor_icon(), Some(CursorIcon::Default));
+
+        // glyph atlases rebuilt after the widgets aren't hit-tested
+        assert!(engine.set_dpi_scale_factor(2.0).is_ok());
+        engine.update(Some(mouse_at(50.0, 50.0)), &None);
+        assert_eq!(engine.cursor_icon(), Some(CursorIcon::Pointer));
+        assert_eq!(
+            engine.hovered_cursor_icon(Position { x: 5.0, y: 300.0 }),
+            CursorIcon::Default
+        );
+    }
+
+    #[test]
+    fn app_cursor_settings_outlast_updates() {
+        let Some(mut engine) = PlutoniumEngine::for_tests(64, 64) else {
+            return;
+        };
+        let mouse = mouse_at(5.0, 5.0);
+        engine.update(Some(mouse), &None);
+        assert_eq!(engine.cursor_icon(), Some(CursorIcon::Default));
+
+        engine.set_cursor_icon(CursorIcon::Grab);
+        engine.update(Some(mouse), &None);
+        assert_eq!(engine.cursor_icon(), Some(CursorIcon::Grab));
+
+        engine.set_cursor_visible(false);
+        engine.update(Some(mouse), &None);
+        assert_eq!(engine.cursor_icon(), None);
+
+        engine.set_cursor_visible(true);
+        engine.clear_cursor_icon();
+        assert_eq!(engine.cursor_icon(), Some(CursorIcon::Default));
+    }
+
+    #[test]
+    fn measure_text_stays_logical_across_dpi_changes() {
+        let Some(mut engine) = PlutoniumEngine::for_tests(64, 64) else {
+            return;
+        };
+        assert!(engine.load_font(ROBOTO, 24.0, "roboto").is_ok());
+        let width = engine.measure_text("Hello, world", "roboto");
+        assert!(width > 0.0);
+
+        assert!(engine.set_dpi_scale_factor(2.0).is_ok());
+        let hidpi_width = engine.measure_text("Hello, world", "roboto");
+        // glyphs rasterize at double the pixels, so only rounding differs
+        assert!(
+            (hidpi_width - width).abs() < 2.0,
+            "{width} vs {hidpi_width}"
+        );
+    }
+
+    #[test]
+    fn dpi_changes_remeasure_text2d() {
+        let Some(mut engine) = PlutoniumEngine::for_tests(64, 64) else {
+            return;
+        };
+        assert!(engine.load_font(ROBOTO, 13.0, "roboto").is_ok());
+        let text = "The quick brown fox jumps over the lazy dog";
+        let label = engine.create_text2d(text, "roboto", 13.0, Position::default());
+        let width = label.get_dimensions().width;
+
+        assert!(engine.set_dpi_scale_factor(1.37).is_ok());
+        let rescaled = engine.measure_text(text, "roboto");
+        assert_ne!(rescaled, width);
+        assert_eq!(label.get_dimensions().width, rescaled);
+    }
+
+    #[test]
+    fn try_create_texture_2d_reports_missing_files() {
+        let Some(mut engine) = PlutoniumEngine::for_tests(64, 64) else {
+            return;
+        };
+        let result = engine.try_create_texture_2d("missing.svg", Position::default(), 1.0);
+        assert!(matches!(result, Err(EngineError::Io { .. })));
+        assert!(engine.object_ids().is_empty());
+
+        let texture = engine.try_create_texture_2d(SQUARE_SVG, Position::default(), 1.0);
+        assert!(texture.is_ok());
+        assert_eq!(engine.object_ids().len(), 1);
+    }
+
+    #[test]
+    fn try_create_texture_2d_reports_malformed_svg() {
+        let Some(mut engine) = PlutoniumEngine::for_tests(64, 64) else {
+            return;
+        };
+        let svg_path = std::env::temp_dir().join(format!("{}.svg", Uuid::new_v4()));
+        std::fs::write(&svg_path, "<svg xmlns=\"http://www.w3.org/2000/svg\"><rect").unwrap();
+        let result =
+            engine.try_create_texture_2d(svg_path.to_str().unwrap(), Position::default(), 1.0);
+        std::fs::remove_file(&svg_path).unwrap();
+
+        assert!(matches!(result, Err(EngineError::SvgParse(_))));
+        assert!(engine.object_ids().is_empty());
+    }
+
+    #[test]
+    fn tile_names_reach_the_atlas_cache() {
+        let Some(mut engine) = PlutoniumEngine::for_tests(64, 64) else {
+            return;
+        };
+        let cache_path = std::env::temp_dir().join(format!("{}.atlas", Uuid::new_v4()));
+        let cache_path = cache_path.to_str().unwrap();
+        let create = |engine: &mut PlutoniumEngine| {
+            engine
+                .create_texture_atlas_cached(
+                    SQUARE_SVG,
+                    cache_path,
+                    Position::default(),
+                    1.0,
+                    Size::new(50.0, 50.0),
+                    wgpu::FilterMode::Linear,
+                )
+                .unwrap()
+                .0
+        };
+
+        let atlas = create(&mut engine);
+        engine.name_atlas_tile(&atlas, "corner", 3);
+        let reloaded = create(&mut engine);
+        std::fs::remove_file(cache_path).unwrap();
+        assert_eq!(engine.tile_index_by_name(&reloaded, "corner"), Some(3));
+    }
+
+    #[cfg(feature = "retain-cpu-textures")]
+    #[test]
+    fn recreate_device_reuploads_retained_pixels() {
+        let Some(mut engine) = PlutoniumEngine::for_tests(64, 64) else {
+            return;
+        };
+        let (texture_key, _) = engine.create_texture_svg(SQUARE_SVG, Position::default(), 1.0);
+        let atlas_id = square_atlas(&mut engine);
+        assert!(engine.load_font(ROBOTO, 16.0, "roboto").is_ok());
+        let batch = engine.build_static_atlas_batch(&atlas_id, &[(0, Position::default())]);
+        engine.draw_static_batch(&batch);
+
+        engine.device.destroy();
+        engine.device.poll(wgpu::Maintain::Wait);
+        assert!(engine.is_device_lost());
+
+        assert!(engine.recreate_device().is_ok());
+        assert!(!engine.is_device_lost());
+        engine.queue_texture(&texture_key, Some(Position::default()));
+        engine.queue_tile(&atlas_id, 3, Position { x: 32.0, y: 32.0 });
+        engine.draw_static_batch(&batch);
+        engine.queue_text("Hi", "roboto", Position::default());
+        engine.render().unwrap();
+
+        // the center of square.svg is blue
+        let pixels = engine.read_pixels().unwrap();
+        let center = (20 * 64 + 20) * 4;
+        assert_eq!(&pixels[center..center + 3], &[0, 0, 255]);
+    }
+
+    #[test]
+    fn dpi_change_replaces_font_atlases() {
+        let Some(mut engine) = PlutoniumEngine::for_tests(64, 64) else {
+            return;
+        };
+        assert!(engine.load_font(ROBOTO, 24.0, "roboto").is_ok());
+        let old_atlas = engine.text_renderer.font_atlas_id("roboto").unwrap();
+
+        assert!(engine.set_dpi_scale_factor(2.0).is_ok());
+        let new_atlas = engine.text_renderer.font_atlas_id("roboto").unwrap();
+        assert_ne!(old_atlas, new_atlas);
+        assert!(!engine.atlas_map.contains_key(&old_atlas));
+        assert!(engine.atlas_map.contains_key(&new_atlas));
+        assert!(engine.object_ids().is_empty());
+    }
+
+    #[test]
+    fn failed_font_reload_keeps_the_old_fonts() {
+        let Some(mut engine) = PlutoniumEngine::for_tests(64, 64) else {
+            return;
+        };
+        assert!(engine.load_font(ROBOTO, 24.0, "roboto").is_ok());
+        assert!(engine.load_font(ROBOTO, 12.0, "small").is_ok());
+        let width = engine.measure_text("Hello, world", "roboto");
+        let atlas = engine.text_renderer.font_atlas_id("roboto").unwrap();
+        engine.loaded_fonts.get_mut("small").unwrap().path = "missing.ttf".to_string();
+
+        assert!(matches!(
+            engine.set_dpi_scale_factor(2.0),
+            Err(FontError::IoError(_))
+        ));
+        assert_eq!(engine.dpi_scale_factor(), 1.0);
+        assert_eq!(engine.loaded_fonts.len(), 2);
+        assert_eq!(engine.text_renderer.font_atlas_id("roboto"), Some(atlas));
+        assert_eq!(engine.measure_text("Hello, world", "roboto"), width);
+    }
 }
@@ -0,0 +1,281 @@
+use crate::utils::{DrawParams, Position};
+use crate::PlutoniumEngine;
+use uuid::Uuid;
+
+/// How an emitter spawns particles; ranges are sampled uniformly per particle.
+#[derive(Debug, Clone, Copy)]
+pub struct EmitterConfig {
+    pub position: Position,
+    /// Particles spawned per second while updating; zero for burst-only emitters.
+    pub rate: f32,
+    pub lifetime: (f32, f32),
+    pub velocity_min: Position,
+    pub velocity_max: Position,
+    /// RGBA tint, 0..=1, sampled per channel between the two colors.
+    pub color_min: [f32; 4],
+    pub color_max: [f32; 4],
+    /// Atlas tile drawn for every particle.
+    pub tile_index: usize,
+    pub scale: f32,
+}
+
+impl Default for EmitterConfig {
+    fn default() -> Self {
+        EmitterConfig {
+            position: Position::default(),
+            rate: 0.0,
+            lifetime: (1.0, 1.0),
+            velocity_min: Position::default(),
+            velocity_max: Position::default(),
+            color_min: [1.0; 4],
+            color_max: [1.0; 4],
+            tile_index: 0,
+            scale: 1.0,
+        }
+    }
+}
+
+/// CPU-side particles drawn from one atlas with a single instanced draw each frame.
+///
+/// Particle data lives in parallel arrays with the live particles packed at the front, so a
+/// retired particle's slot is reused by the next spawn instead of reallocating. Spawns draw
+/// from a seeded generator, so the same seed and `update` sequence replays identically.
+pub struct ParticleSystem {
+    atlas_id: Uuid,
+    emitter: EmitterConfig,
+    positions: Vec<Position>,
+    velocities: Vec<Position>,
+    colors: Vec<[f32; 4]>,
+    ages: Vec<f32>,
+    lifetimes: Vec<f32>,
+    alive: usize,
+    spawn_accumulator: f32,
+    rng_state: u64,
+}
+
+impl ParticleSystem {
+    pub fn new(atlas_id: Uuid, emitter: EmitterConfig, seed: u64) -> Self {
+        Self {
+            atlas_id,
+            emitter,
+            positions: Vec::new(),
+            velocities: Vec::new(),
+            colors: Vec::new(),
+            ages: Vec::new(),
+            lifetimes: Vec::new(),
+            alive: 0,
+            spawn_accumulator: 0.0,
+            rng_state: seed,
+        }
+    }
+
+    pub fn emitter(&self) -> &EmitterConfig {
+        &self.emitter
+    }
+
+    pub fn emitter_mut(&mut self) -> &mut EmitterConfig {
+        &mut self.emitter
+    }
+
+    pub fn len(&self) -> usize {
+        self.alive
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.alive == 0
+    }
+
+    /// Positions of the live particles.
+    pub fn positions(&self) -> &[Position] {
+        &self.positions[..self.alive]
+    }
+
+    /// Tints of the live particles, in the same order as `positions`.
+    pub fn colors(&self) -> &[[f32; 4]] {
+        &self.colors[..self.alive]
+    }
+
+    /// Spawns `count` particles at the emitter immediately.
+    pub fn burst(&mut self, count: usize) {
+        for _ in 0..count {
+            self.spawn();
+        }
+    }
+
+    /// Retires every live particle; their slots are kept for reuse.
+    pub fn clear(&mut self) {
+        self.alive = 0;
+        self.spawn_accumulator = 0.0;
+    }
+
+    /// Ages and moves live particles by `dt` seconds, retires expired ones, then spawns at `rate`.
+    pub fn update(&mut self, dt: f32) {
+        let mut i = 0;
+        while i < self.alive {
+            self.ages[i] += dt;
+            if self.ages[i] >= self.lifetimes[i] {
+                self.retire(i);
+                continue;
+            }
+            self.positions[i].x += self.velocities[i].x * dt;
+            self.positions[i].y += self.velocities[i].y * dt;
+            i += 1;
+        }
+
+        if self.emitter.rate > 0.0 {
+            self.spawn_accumulator += self.emitter.rate * dt;
+            while self.spawn_accumulator >= 1.0 {
+                self.spawn_accumulator -= 1.0;
+                self.spawn();
+            }
+        }
+    }
+
+    pub fn render(&self, engine: &mut PlutoniumEngine) {
+        let params = DrawParams::with_scale(self.emitter.scale);
+        let tiles: Vec<(usize, Position, DrawParams, [f32; 4])> = self
+            .positions()
+            .iter()
+            .zip(self.colors())
+            .map(|(position, color)| (self.emitter.tile_index, *position, params, *color))
+            .collect();
+        engine.draw_tile_instances(&self.atlas_id, &tiles);
+    }
+
+    fn spawn(&mut self) {
+        let velocity = Position {
+            x: self.sample(self.emitter.velocity_min.x, self.emitter.velocity_max.x),
+            y: self.sample(self.emitter.velocity_min.y, self.emitter.velocity_max.y),
+        };
+        let (color_min, color_max) = (self.emitter.color_min, self.emitter.color_max);
+        let color =
+            std::array::from_fn(|channel| self.sample(color_min[channel], color_max[channel]));
+        let lifetime = self.sample(self.emitter.lifetime.0, self.emitter.lifetime.1);
+
+        let i = self.alive;
+        if i == self.positions.len() {
+            self.positions.push(self.emitter.position);
+            self.velocities.push(velocity);
+            self.colors.push(color);
+            self.ages.push(0.0);
+            self.lifetimes.push(lifetime);
+        } else {
+            self.positions[i] = self.emitter.position;
+            self.velocities[i] = velocity;
+            self.colors[i] = color;
+            self.ages[i] = 0.0;
+            self.lifetimes[i] = lifetime;
+        }
+        self.alive += 1;
+    }
+
+    /// Moves the last live particle into slot `i`, leaving the retired one in the dead tail.
+    fn retire(&mut self, i: usize) {
+        self.alive -= 1;
+        self.positions.swap(i, self.alive);
+        self.velocities.swap(i, self.alive);
+        self.colors.swap(i, self.alive);
+        self.ages.swap(i, self.alive);
+        self.lifetimes.swap(i, self.alive);
+    }
+
+    /// Uniform sample in `[min, max]` from a splitmix64 stream.
+    fn sample(&mut self, min: f32, max: f32) -> f32 {
+        self.rng_state = self.rng_state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.rng_state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        let unit = (z >> 40) as f32 / (1u64 << 24) as f32;
+        min + (max - min) * unit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn emitter() -> EmitterConfig {
+        EmitterConfig {
+            position: Position { x: 10.0, y: 20.0 },
+            lifetime: (1.0, 3.0),
+            velocity_min: Position { x: -5.0, y: 2.0 },
+            velocity_max: Position { x: 5.0, y: 8.0 },
+            color_min: [0.2, 0.0, 0.5, 1.0],
+            color_max: [0.4, 1.0, 0.5, 1.0],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn update_moves_by_velocity_times_dt() {
+        let mut particles = ParticleSystem::new(Uuid::nil(), emitter(), 7);
+        particles.burst(3);
+        let before = particles.positions().to_vec();
+
+        particles.update(0.5);
+        let moved = particles.positions().iter().zip(&particles.velocities);
+        for ((after, velocity), before) in moved.zip(before) {
+            assert!((after.x - (before.x + velocity.x * 0.5)).abs() < 1e-5);
+            assert!((after.y - (before.y + velocity.y * 0.5)).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn update_retires_expired_particles() {
+        let mut particles = ParticleSystem::new(Uuid::nil(), emitter(), 7);
+        particles.burst(20);
+        let lifetimes = particles.lifetimes.clone();
+        let survivors = lifetimes.iter().filter(|lifetime| **lifetime > 2.0).count();
+
+        particles.update(2.0);
+        assert_eq!(particles.len(), survivors);
+        assert!(particles.lifetimes[..particles.len()]
+            .iter()
+            .all(|lifetime| *lifetime > 2.0));
+
+        particles.update(1.0);
+        assert!(particles.is_empty());
+    }
+
+    #[test]
+    fn spawns_reuse_retired_slots() {
+        let mut particles = ParticleSystem::new(Uuid::nil(), emitter(), 7);
+        particles.burst(5);
+        particles.update(3.0);
+        assert!(particles.is_empty());
+
+        particles.burst(5);
+        assert_eq!(particles.len(), 5);
+        assert_eq!(particles.positions.len(), 5);
+    }
+
+    #[test]
+    fn same_seed_replays_identically() {
+        let mut a = ParticleSystem::new(Uuid::nil(), emitter(), 42);
+        let mut b = ParticleSystem::new(Uuid::nil(), emitter(), 42);
+        a.emitter_mut().rate = 30.0;
+        b.emitter_mut().rate = 30.0;
+        for _ in 0..10 {
+            a.update(0.1);
+            b.update(0.1);
+        }
+        assert_eq!(a.len(), b.len());
+        for (pa, pb) in a.positions().iter().zip(b.positions()) {
+            assert_eq!((pa.x, pa.y), (pb.x, pb.y));
+        }
+        assert_eq!(a.colors(), b.colors());
+    }
+
+    #[test]
+    fn colors_are_sampled_per_channel_within_range() {
+        let mut particles = ParticleSystem::new(Uuid::nil(), emitter(), 3);
+        particles.burst(50);
+        for color in particles.colors() {
+            assert!((0.2..=0.4).contains(&color[0]));
+            assert!((0.0..=1.0).contains(&color[1]));
+            assert_eq!(color[2], 0.5);
+            assert_eq!(color[3], 1.0);
+        }
+    }
+}
@@ -2,13 +2,14 @@ use crate::pluto_objects::text2d::Text2D;
 use crate::text::TextRenderer;
 use crate::texture_svg::TextureSVG;
 use crate::traits::{PlutoObject, UpdateContext};
-use crate::utils::{MouseInfo, Position, Rectangle};
+use crate::utils::{DrawParams, MouseInfo, Position, Rectangle};
 use crate::PlutoniumEngine;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
 use uuid::Uuid;
 use winit::keyboard::Key;
+use winit::window::CursorIcon;
 
 // Internal Representation
 pub struct ButtonInternal {
@@ -16,8 +17,17 @@ pub struct ButtonInternal {
     texture_key: Uuid,
     text_object: Text2D,
     dimensions: Rectangle,
-    callback: Option<Box<dyn Fn()>>,
+    callback: Option<Box<dyn FnMut()>>,
     _padding: f32, // Currently unused but could affect positioning
+    hover_scale: Option<f32>,
+    press_scale: Option<f32>,
+    hovered: bool,
+    pressed: bool,
+    /// Left button state from the previous update, to find the frame a press begins.
+    mouse_down: bool,
+    /// Whether the current press began over the button; presses dragged in from outside don't count.
+    press_started_inside: bool,
+    visible: bool,
 }
 
 impl ButtonInternal {
@@ -33,8 +43,15 @@ impl ButtonInternal {
             texture_key,
             dimensions,
             text_object,
-            callback,
+            callback: callback.map(|callback| callback as Box<dyn FnMut()>),
             _padding: 0.0,
+            hover_scale: None,
+            press_scale: None,
+            hovered: false,
+            pressed: false,
+            mouse_down: false,
+            press_started_inside: false,
+            visible: true,
         }
     }
 
@@ -47,21 +64,59 @@ impl ButtonInternal {
     }
 
     pub fn set_callback(&mut self, callback: Option<Box<dyn Fn()>>) {
-        self.callback = callback;
+        self.callback = callback.map(|callback| callback as Box<dyn FnMut()>);
+    }
+
+    /// Calls `on_click` once each time the button is pressed; replaces any earlier callback.
+    pub fn on_click(&mut self, on_click: impl FnMut() + 'static) {
+        self.callback = Some(Box::new(on_click));
+    }
+
+    /// Background scale while the mouse is over the button; `None` keeps it at 1.
+    pub fn set_hover_scale(&mut self, hover_scale: Option<f32>) {
+        self.hover_scale = hover_scale;
+    }
+
+    /// Background scale while the button is held down; falls back to the hover scale.
+    pub fn set_press_scale(&mut self, press_scale: Option<f32>) {
+        self.press_scale = press_scale;
+    }
+
+    /// Scale the background is currently drawn at, from the last `update`'s hover/press state.
+    pub fn current_scale(&self) -> f32 {
+        let hover_scale = self.hover_scale.filter(|_| self.hovered);
+        let press_scale = self.press_scale.filter(|_| self.pressed);
+        press_scale.or(hover_scale).unwrap_or(1.0)
     }
 
     pub fn render(&self, engine: &mut PlutoniumEngine) {
-        engine.queue_texture(&self.texture_key, Some(self.dimensions.pos()));
+        if !self.visible {
+            return;
+        }
+        engine.queue_texture_with_params(
+            &self.texture_key,
+            Some(self.dimensions.pos()),
+            DrawParams::with_scale(self.current_scale()),
+        );
         self.text_object.render(engine);
     }
 
     pub fn update(&mut self, mouse_info: Option<MouseInfo>, _key_pressed: &Option<Key>) {
         if let Some(mouse) = mouse_info {
-            if mouse.is_lmb_clicked && self.dimensions.contains(mouse.mouse_pos) {
-                if let Some(ref callback) = self.callback {
-                    callback();
+            self.hovered = self.dimensions.contains(mouse.mouse_pos);
+            let press_began = mouse.is_lmb_clicked && !self.mouse_down;
+            self.mouse_down = mouse.is_lmb_clicked;
+            if press_began {
+                self.press_started_inside = self.hovered;
+                if self.hovered {
+                    if let Some(callback) = self.callback.as_mut() {
+                        callback();
+                    }
                 }
+            } else if !mouse.is_lmb_clicked {
+                self.press_started_inside = false;
             }
+            self.pressed = self.press_started_inside && self.hovered;
         }
     }
 }
@@ -71,6 +126,10 @@ impl PlutoObject for ButtonInternal {
         self.id
     }
 
+    fn cursor_icon(&self) -> Option<CursorIcon> {
+        Some(CursorIcon::Pointer)
+    }
+
     fn texture_key(&self) -> Uuid {
         self.texture_key
     }
@@ -83,6 +142,10 @@ impl PlutoObject for ButtonInternal {
         self.dimensions.pos()
     }
 
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
     fn set_dimensions(&mut self, new_dimensions: Rectangle) {
         self.dimensions = new_dimensions;
     }
@@ -91,6 +154,15 @@ impl PlutoObject for ButtonInternal {
         self.dimensions.set_pos(new_position);
     }
 
+    fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+        self.text_object.set_visible(visible);
+    }
+
+    fn render(&self, engine: &mut PlutoniumEngine) {
+        self.render(engine);
+    }
+
     fn update(
         &mut self,
         mouse_info: Option<MouseInfo>,
@@ -126,6 +198,22 @@ impl Button {
         self.internal.borrow_mut().set_callback(callback);
     }
 
+    pub fn on_click(&self, on_click: impl FnMut() + 'static) {
+        self.internal.borrow_mut().on_click(on_click);
+    }
+
+    pub fn set_hover_scale(&self, hover_scale: Option<f32>) {
+        self.internal.borrow_mut().set_hover_scale(hover_scale);
+    }
+
+    pub fn set_press_scale(&self, press_scale: Option<f32>) {
+        self.internal.borrow_mut().set_press_scale(press_scale);
+    }
+
+    pub fn current_scale(&self) -> f32 {
+        self.internal.borrow().current_scale()
+    }
+
     pub fn render(&self, engine: &mut PlutoniumEngine) {
         self.internal.borrow().render(engine);
     }
@@ -152,4 +240,104 @@ impl Button {
     pub fn set_pos(&self, position: Position) {
         self.internal.borrow_mut().set_pos(position);
     }
+
+    pub fn is_visible(&self) -> bool {
+        self.internal.borrow().is_visible()
+    }
+
+    pub fn set_visible(&self, visible: bool) {
+        self.internal.borrow_mut().set_visible(visible);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pluto_objects::text2d::Text2DInternal;
+
+    fn button() -> ButtonInternal {
+        let dimensions = Rectangle::new(0.0, 0.0, 100.0, 40.0);
+        let text = Text2DInternal::new(Uuid::new_v4(), "font".to_string(), dimensions, 16.0, "OK");
+        ButtonInternal::new(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            dimensions,
+            Text2D::new(Rc::new(RefCell::new(text))),
+            None,
+        )
+    }
+
+    fn mouse(x: f32, y: f32, lmb: bool) -> Option<MouseInfo> {
+        Some(MouseInfo {
+            is_rmb_clicked: false,
+            is_lmb_clicked: lmb,
+            is_mmb_clicked: false,
+            mouse_pos: Position { x, y },
+        })
+    }
+
+    fn counting_button() -> (ButtonInternal, Rc<RefCell<u32>>) {
+        let clicks = Rc::new(RefCell::new(0));
+        let mut button = button();
+        let counter = Rc::clone(&clicks);
+        button.on_click(move || *counter.borrow_mut() += 1);
+        (button, clicks)
+    }
+
+    #[test]
+    fn click_fires_once_per_press() {
+        let (mut button, clicks) = counting_button();
+        button.update(mouse(50.0, 20.0, false), &None);
+        button.update(mouse(50.0, 20.0, true), &None);
+        button.update(mouse(50.0, 20.0, true), &None);
+        button.update(mouse(60.0, 20.0, true), &None);
+        assert_eq!(*clicks.borrow(), 1);
+
+        button.update(mouse(50.0, 20.0, false), &None);
+        button.update(mouse(50.0, 20.0, true), &None);
+        assert_eq!(*clicks.borrow(), 2);
+    }
+
+    #[test]
+    fn press_dragged_in_from_outside_does_not_click() {
+        let (mut button, clicks) = counting_button();
+        button.update(mouse(200.0, 20.0, true), &None);
+        button.update(mouse(50.0, 20.0, true), &None);
+        assert_eq!(*clicks.borrow(), 0);
+        assert!(!button.pressed);
+    }
+
+    #[test]
+    fn leaving_and_reentering_while_held_does_not_click_again() {
+        let (mut button, clicks) = counting_button();
+        button.update(mouse(50.0, 20.0, true), &None);
+        button.update(mouse(200.0, 20.0, true), &None);
+        assert!(!button.pressed);
+        button.update(mouse(50.0, 20.0, true), &None);
+        assert!(button.pressed);
+        assert_eq!(*clicks.borrow(), 1);
+    }
+
+    #[test]
+    fn current_scale_prefers_press_over_hover() {
+        let mut button = button();
+        button.set_hover_scale(Some(1.1));
+        button.set_press_scale(Some(0.9));
+        assert_eq!(button.current_scale(), 1.0);
+
+        button.update(mouse(50.0, 20.0, false), &None);
+        assert_eq!(button.current_scale(), 1.1);
+        button.update(mouse(50.0, 20.0, true), &None);
+        assert_eq!(button.current_scale(), 0.9);
+        button.update(mouse(200.0, 20.0, true), &None);
+        assert_eq!(button.current_scale(), 1.0);
+    }
+
+    #[test]
+    fn current_scale_falls_back_to_hover_while_pressed() {
+        let mut button = button();
+        button.set_hover_scale(Some(1.1));
+        button.update(mouse(50.0, 20.0, true), &None);
+        assert_eq!(button.current_scale(), 1.1);
+    }
 }
@@ -9,7 +9,7 @@ use crate::traits::{PlutoObject, UpdateContext};
 use crate::utils::{MouseInfo, Position, Rectangle};
 use crate::PlutoniumEngine;
 
-use crate::text::TextRenderer;
+use crate::text::{LineHeight, TextRenderer};
 // Text2D Implementation
 pub struct Text2DInternal {
     id: Uuid,
@@ -18,6 +18,8 @@ pub struct Text2DInternal {
     font_size: f32,
     content: String,
     content_changed: bool,
+    line_height: LineHeight,
+    visible: bool,
 }
 
 impl Text2DInternal {
@@ -35,6 +37,8 @@ impl Text2DInternal {
             font_size,
             content: content.to_string(),
             content_changed: false,
+            line_height: LineHeight::default(),
+            visible: true,
         }
     }
 
@@ -62,6 +66,38 @@ impl Text2DInternal {
         self.content.pop();
     }
 
+    /// Replaces the content and re-measures right away instead of waiting for the next `update`.
+    pub fn set_text(
+        &mut self,
+        new_text: &str,
+        text_renderer: &TextRenderer,
+        dpi_scale_factor: f32,
+    ) {
+        self.content = new_text.to_string();
+        self.remeasure(text_renderer, dpi_scale_factor);
+    }
+
+    /// Sizes the bounds to the content; empty text takes up no space.
+    fn remeasure(&mut self, text_renderer: &TextRenderer, dpi_scale_factor: f32) {
+        if self.content.is_empty() {
+            self.dimensions.width = 0.0;
+            self.dimensions.height = 0.0;
+        } else {
+            self.dimensions.width =
+                text_renderer.measure_text(&self.content, &self.font_key, dpi_scale_factor);
+            self.dimensions.height = self.font_size;
+        }
+        self.content_changed = false;
+    }
+
+    pub fn get_line_height(&self) -> LineHeight {
+        self.line_height
+    }
+
+    pub fn set_line_height(&mut self, line_height: LineHeight) {
+        self.line_height = line_height;
+    }
+
     pub fn get_text(&self) -> &str {
         &self.content
     }
@@ -88,6 +124,10 @@ impl PlutoObject for Text2DInternal {
         self.dimensions.pos()
     }
 
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
     fn set_dimensions(&mut self, new_dimensions: Rectangle) {
         self.dimensions = new_dimensions;
     }
@@ -96,25 +136,37 @@ impl PlutoObject for Text2DInternal {
         self.dimensions.set_pos(new_position);
     }
 
+    fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+
+    fn rescale(&mut self, text_renderer: &TextRenderer, dpi_scale_factor: f32) {
+        self.remeasure(text_renderer, dpi_scale_factor);
+    }
+
     fn update(
         &mut self,
         _mouse_info: Option<MouseInfo>,
         _key_pressed: &Option<Key>,
         _texture_map: &mut HashMap<Uuid, TextureSVG>,
         _update_context: Option<UpdateContext>,
-        _dpi_scale_factor: f32,
-        text_renderer: &TextRenderer, // Add this parameter
+        dpi_scale_factor: f32,
+        text_renderer: &TextRenderer,
     ) {
         if self.content_changed {
-            let width = text_renderer // Use the parameter directly
-                .measure_text(&self.content, &self.font_key);
-            self.dimensions.width = width;
-            self.dimensions.height = self.font_size;
-            self.content_changed = false;
+            self.remeasure(text_renderer, dpi_scale_factor);
         }
     }
     fn render(&self, engine: &mut PlutoniumEngine) {
-        engine.queue_text(&self.content, &self.font_key, self.dimensions.pos());
+        if !self.visible {
+            return;
+        }
+        engine.queue_text_with_line_height(
+            &self.content,
+            &self.font_key,
+            self.dimensions.pos(),
+            self.line_height,
+        );
     }
 }
 
@@ -139,6 +191,13 @@ impl Text2D {
         self.internal.borrow_mut().set_content(content);
     }
 
+    /// Like `set_content`, but `get_dimensions` reflects the new text immediately.
+    pub fn set_text(&self, engine: &PlutoniumEngine, text: &str) {
+        self.internal
+            .borrow_mut()
+            .set_text(text, &engine.text_renderer, engine.dpi_scale_factor);
+    }
+
     pub fn append_content(&self, content: &str) {
         self.internal.borrow_mut().append_content(content);
     }
@@ -151,6 +210,18 @@ impl Text2D {
         self.internal.borrow().get_font_size()
     }
 
+    pub fn get_font(&self) -> String {
+        self.internal.borrow().get_font().to_string()
+    }
+
+    pub fn set_line_height(&self, line_height: LineHeight) {
+        self.internal.borrow_mut().set_line_height(line_height);
+    }
+
+    pub fn get_line_height(&self) -> LineHeight {
+        self.internal.borrow().get_line_height()
+    }
+
     pub fn get_dimensions(&self) -> Rectangle {
         self.internal.borrow().dimensions()
     }
@@ -159,6 +230,14 @@ impl Text2D {
         self.internal.borrow().pos()
     }
 
+    pub fn is_visible(&self) -> bool {
+        self.internal.borrow().is_visible()
+    }
+
+    pub fn set_visible(&self, visible: bool) {
+        self.internal.borrow_mut().set_visible(visible);
+    }
+
     pub fn set_pos(&self, position: Position) {
         self.internal.borrow_mut().set_pos(position);
     }
@@ -175,3 +254,31 @@ impl Text2D {
         self.internal.borrow().get_id()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::Position;
+    use crate::PlutoniumEngine;
+
+    const ROBOTO: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/media/roboto.ttf");
+
+    #[test]
+    fn set_text_remeasures_immediately() {
+        let Some(mut engine) = PlutoniumEngine::for_tests(64, 64) else {
+            return;
+        };
+        assert!(engine.load_font(ROBOTO, 20.0, "roboto").is_ok());
+        let text = engine.create_text2d("1", "roboto", 20.0, Position::default());
+        let short = text.get_dimensions();
+
+        text.set_text(&engine, "1000000");
+        let long = text.get_dimensions();
+        assert!(long.width > short.width);
+        assert_eq!(long.width, engine.measure_text("1000000", "roboto"));
+        assert_eq!(long.height, 20.0);
+
+        text.set_text(&engine, "");
+        let empty = text.get_dimensions();
+        assert_eq!((empty.width, empty.height), (0.0, 0.0));
+    }
+}
@@ -8,6 +8,7 @@ use std::collections::HashMap;
 use std::rc::Rc;
 use uuid::Uuid;
 use winit::keyboard::{Key, NamedKey};
+use winit::window::CursorIcon;
 
 pub struct TextInputInternal {
     id: Uuid,
@@ -16,6 +17,8 @@ pub struct TextInputInternal {
     cursor: Text2D, // Owned directly
     dimensions: Rectangle,
     focused: bool,
+    composing: Option<String>,
+    visible: bool,
 }
 
 impl TextInputInternal {
@@ -33,6 +36,8 @@ impl TextInputInternal {
             cursor,
             dimensions,
             focused: false,
+            composing: None,
+            visible: true,
         }
     }
 
@@ -46,6 +51,33 @@ impl TextInputInternal {
 
     pub fn clear(&mut self) {
         self.text.set_content("");
+        self.composing = None;
+    }
+
+    /// In-progress IME text shown after the committed content; empty or `None` hides it.
+    pub fn set_composing(&mut self, preedit: Option<&str>) {
+        self.composing = preedit
+            .filter(|preedit| !preedit.is_empty())
+            .map(str::to_string);
+    }
+
+    pub fn composing(&self) -> Option<&str> {
+        self.composing.as_deref()
+    }
+
+    /// Replaces the composition with the IME's final string.
+    pub fn commit_composition(&mut self, committed: &str) {
+        self.composing = None;
+        self.text.append_content(committed);
+    }
+
+    /// Committed content followed by any in-progress composition, as it appears on screen.
+    pub fn displayed_text(&self) -> String {
+        let mut displayed = self.text.get_content();
+        if let Some(composing) = &self.composing {
+            displayed.push_str(composing);
+        }
+        displayed
     }
 
     pub fn set_font_size(&mut self, font_size: f32) {
@@ -71,9 +103,34 @@ impl PlutoObject for TextInputInternal {
         self.id
     }
 
+    fn cursor_icon(&self) -> Option<CursorIcon> {
+        Some(CursorIcon::Text)
+    }
+
     fn render(&self, engine: &mut PlutoniumEngine) {
+        if !self.visible {
+            return;
+        }
         self.button.render(engine);
         self.text.render(engine);
+        if let Some(composing) = &self.composing {
+            // no line primitive yet, so the underline is a run of underscores under the preedit
+            let font_key = self.text.get_font();
+            let text_dimensions = self.text.get_dimensions();
+            let position = Position {
+                x: text_dimensions.x + text_dimensions.width,
+                y: text_dimensions.y,
+            };
+            let composing_width = engine.measure_text(composing, &font_key);
+            let underscore_width = engine.measure_text("_", &font_key);
+            let underscores = if underscore_width > 0.0 {
+                (composing_width / underscore_width).ceil() as usize
+            } else {
+                composing.chars().count()
+            };
+            engine.queue_text(composing, &font_key, position);
+            engine.queue_text(&"_".repeat(underscores), &font_key, position);
+        }
         self.cursor.render(engine);
     }
 
@@ -105,6 +162,10 @@ impl PlutoObject for TextInputInternal {
         self.dimensions.pos()
     }
 
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
     fn set_dimensions(&mut self, new_dimensions: Rectangle) {
         self.dimensions = new_dimensions;
     }
@@ -112,6 +173,14 @@ impl PlutoObject for TextInputInternal {
     fn set_pos(&mut self, new_position: Position) {
         self.dimensions.set_pos(new_position);
     }
+
+    fn set_visible(&mut self, visible: bool) {
+        // the parts are registered objects of their own, so hide them too
+        self.visible = visible;
+        self.button.set_visible(visible);
+        self.text.set_visible(visible);
+        self.cursor.set_visible(visible);
+    }
 }
 
 pub struct TextInput {
@@ -149,6 +218,34 @@ impl TextInput {
         self.internal.borrow_mut().set_focus(focus);
     }
 
+    pub fn set_composing(&self, preedit: Option<&str>) {
+        self.internal.borrow_mut().set_composing(preedit);
+    }
+
+    pub fn get_composing(&self) -> Option<String> {
+        self.internal.borrow().composing().map(str::to_string)
+    }
+
+    pub fn commit_composition(&self, committed: &str) {
+        self.internal.borrow_mut().commit_composition(committed);
+    }
+
+    pub fn get_content(&self) -> String {
+        self.internal.borrow().text.get_content()
+    }
+
+    pub fn displayed_text(&self) -> String {
+        self.internal.borrow().displayed_text()
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.internal.borrow().is_visible()
+    }
+
+    pub fn set_visible(&self, visible: bool) {
+        self.internal.borrow_mut().set_visible(visible);
+    }
+
     pub fn internal(&self) -> Rc<RefCell<TextInputInternal>> {
         Rc::clone(&self.internal)
     }
@@ -157,3 +254,54 @@ impl TextInput {
         self.internal.borrow().render(engine);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pluto_objects::button::ButtonInternal;
+    use crate::pluto_objects::text2d::Text2DInternal;
+
+    fn text(content: &str) -> Text2D {
+        let dimensions = Rectangle::new(0.0, 0.0, 100.0, 20.0);
+        let internal = Text2DInternal::new(
+            Uuid::new_v4(),
+            "font".to_string(),
+            dimensions,
+            16.0,
+            content,
+        );
+        Text2D::new(Rc::new(RefCell::new(internal)))
+    }
+
+    fn text_input(content: &str) -> TextInput {
+        let dimensions = Rectangle::new(0.0, 0.0, 100.0, 20.0);
+        let button =
+            ButtonInternal::new(Uuid::new_v4(), Uuid::new_v4(), dimensions, text(""), None);
+        let internal = TextInputInternal::new(
+            Uuid::new_v4(),
+            Button::new(Rc::new(RefCell::new(button))),
+            text(content),
+            text("_"),
+            dimensions,
+        );
+        TextInput::new(Rc::new(RefCell::new(internal)))
+    }
+
+    #[test]
+    fn composition_is_displayed_until_committed() {
+        let input = text_input("ab");
+        input.set_composing(Some("ka"));
+        assert_eq!(input.get_composing().as_deref(), Some("ka"));
+        assert_eq!(input.displayed_text(), "abka");
+        assert_eq!(input.get_content(), "ab");
+
+        input.commit_composition("か");
+        assert_eq!(input.get_composing(), None);
+        assert_eq!(input.displayed_text(), "abか");
+        assert_eq!(input.get_content(), "abか");
+
+        // an empty preedit clears the composition rather than showing nothing
+        input.set_composing(Some(""));
+        assert_eq!(input.get_composing(), None);
+    }
+}
@@ -1,5 +1,5 @@
 use crate::traits::PlutoObject;
-use crate::utils::{Position, Rectangle};
+use crate::utils::{DrawParams, Position, Rectangle};
 use crate::PlutoniumEngine;
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -9,6 +9,7 @@ pub struct Texture2DInternal {
     id: Uuid,
     texture_key: Uuid,
     dimensions: Rectangle,
+    visible: bool,
 }
 
 impl Texture2DInternal {
@@ -17,6 +18,7 @@ impl Texture2DInternal {
             id,
             texture_key,
             dimensions,
+            visible: true,
         }
     }
 
@@ -46,6 +48,10 @@ impl PlutoObject for Texture2DInternal {
         self.dimensions.pos()
     }
 
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
     fn set_dimensions(&mut self, new_dimensions: Rectangle) {
         self.set_dimensions(new_dimensions);
     }
@@ -53,6 +59,10 @@ impl PlutoObject for Texture2DInternal {
     fn set_pos(&mut self, new_position: Position) {
         self.set_pos(new_position);
     }
+
+    fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
 }
 
 pub struct Texture2D {
@@ -84,7 +94,23 @@ impl Texture2D {
         self.internal.borrow().pos()
     }
 
+    pub fn is_visible(&self) -> bool {
+        self.internal.borrow().is_visible()
+    }
+
+    pub fn set_visible(&self, visible: bool) {
+        self.internal.borrow_mut().set_visible(visible);
+    }
+
     pub fn render(&self, engine: &mut PlutoniumEngine) {
         self.internal.borrow().render(engine);
     }
+
+    pub fn render_with_params(&self, engine: &mut PlutoniumEngine, params: DrawParams) {
+        let internal = self.internal.borrow();
+        if !internal.is_visible() {
+            return;
+        }
+        engine.queue_texture_with_params(&internal.texture_key(), Some(internal.pos()), params);
+    }
 }
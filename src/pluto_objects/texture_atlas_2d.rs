@@ -1,5 +1,5 @@
 use crate::traits::PlutoObject;
-use crate::utils::{Position, Rectangle, Size};
+use crate::utils::{DrawParams, Position, Rectangle, Size};
 use crate::PlutoniumEngine;
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -11,6 +11,7 @@ pub struct TextureAtlas2DInternal {
     texture_key: Uuid,
     dimensions: Rectangle,
     tile_size: Size,
+    visible: bool,
 }
 
 impl TextureAtlas2DInternal {
@@ -20,6 +21,7 @@ impl TextureAtlas2DInternal {
             texture_key,
             dimensions,
             tile_size,
+            visible: true,
         }
     }
 
@@ -32,8 +34,24 @@ impl TextureAtlas2DInternal {
     }
 
     pub fn render_tile(&self, engine: &mut PlutoniumEngine, tile_index: usize, position: Position) {
+        if !self.visible {
+            return;
+        }
         engine.queue_tile(&self.texture_key, tile_index, position);
     }
+
+    pub fn render_tile_with_params(
+        &self,
+        engine: &mut PlutoniumEngine,
+        tile_index: usize,
+        position: Position,
+        params: DrawParams,
+    ) {
+        if !self.visible {
+            return;
+        }
+        engine.queue_tile_with_params(&self.texture_key, tile_index, position, params);
+    }
 }
 
 impl PlutoObject for TextureAtlas2DInternal {
@@ -53,6 +71,10 @@ impl PlutoObject for TextureAtlas2DInternal {
         self.dimensions.pos()
     }
 
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
     fn set_dimensions(&mut self, new_dimensions: Rectangle) {
         self.set_dimensions(new_dimensions);
     }
@@ -60,6 +82,10 @@ impl PlutoObject for TextureAtlas2DInternal {
     fn set_pos(&mut self, new_position: Position) {
         self.set_pos(new_position);
     }
+
+    fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
 }
 
 // Wrapper Representation
@@ -86,6 +112,18 @@ impl TextureAtlas2D {
             .render_tile(engine, tile_index, position);
     }
 
+    pub fn render_tile_with_params(
+        &self,
+        engine: &mut PlutoniumEngine,
+        tile_index: usize,
+        position: Position,
+        params: DrawParams,
+    ) {
+        self.internal
+            .borrow()
+            .render_tile_with_params(engine, tile_index, position, params);
+    }
+
     pub fn get_id(&self) -> Uuid {
         self.internal.borrow().get_id()
     }
@@ -98,6 +136,14 @@ impl TextureAtlas2D {
         self.internal.borrow().pos()
     }
 
+    pub fn is_visible(&self) -> bool {
+        self.internal.borrow().is_visible()
+    }
+
+    pub fn set_visible(&self, visible: bool) {
+        self.internal.borrow_mut().set_visible(visible);
+    }
+
     pub fn get_tile_size(&self) -> Size {
         self.internal.borrow().tile_size
     }
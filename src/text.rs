@@ -1,7 +1,9 @@
 use crate::pluto_objects::texture_atlas_2d::TextureAtlas2D;
 use crate::utils::{Position, Size};
 use rusttype::{point, Font, Scale};
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::ops::Div;
 use uuid::Uuid;
 
 // Character information for the font atlas
@@ -13,10 +15,67 @@ pub struct CharacterInfo {
     pub size: (u32, u32),
 }
 
+/// Vertical metrics of a loaded font, in the pixel units the atlas was rasterized at.
+#[derive(Clone, Copy, Debug)]
+pub struct FontMetrics {
+    /// Distance from the baseline to the top of the tallest glyphs (positive).
+    pub ascent: f32,
+    /// Distance from the baseline to the bottom of the lowest glyphs (negative).
+    pub descent: f32,
+    /// Extra gap the font recommends between lines.
+    pub line_gap: f32,
+    /// Baseline-to-baseline distance, `ascent - descent + line_gap`.
+    pub line_height: f32,
+}
+
+impl FontMetrics {
+    pub fn new(ascent: f32, descent: f32, line_gap: f32) -> Self {
+        Self {
+            ascent,
+            descent,
+            line_gap,
+            line_height: ascent - descent + line_gap,
+        }
+    }
+}
+
+impl Div<f32> for FontMetrics {
+    type Output = FontMetrics;
+    fn div(self, factor: f32) -> Self::Output {
+        FontMetrics {
+            ascent: self.ascent / factor,
+            descent: self.descent / factor,
+            line_gap: self.line_gap / factor,
+            line_height: self.line_height / factor,
+        }
+    }
+}
+
 pub struct CharacterRenderInfo {
     pub atlas_id: Uuid,
     pub tile_index: usize,
     pub position: Position,
+    /// Index of the glyph's character within the laid out string (in chars, not bytes).
+    pub char_index: usize,
+    pub character: char,
+}
+
+/// Controls how far apart consecutive lines of multiline text are placed.
+#[derive(Debug, Clone, Copy)]
+pub struct LineHeight {
+    /// Multiplier on the font's default line advance.
+    pub multiplier: f32,
+    /// Absolute line advance that replaces the default (and the multiplier) when set.
+    pub absolute: Option<f32>,
+}
+
+impl Default for LineHeight {
+    fn default() -> Self {
+        Self {
+            multiplier: 1.0,
+            absolute: None,
+        }
+    }
 }
 
 pub enum FontError {
@@ -25,6 +84,22 @@ pub enum FontError {
     AtlasRenderError,
 }
 
+#[derive(Debug)]
+pub enum TextError {
+    /// No font has been loaded under this key.
+    FontNotLoaded(String),
+}
+
+impl std::fmt::Display for TextError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TextError::FontNotLoaded(key) => write!(f, "font '{}' is not loaded", key),
+        }
+    }
+}
+
+impl std::error::Error for TextError {}
+
 // Holds font-specific data including its atlas
 pub struct FontAtlas {
     atlas: TextureAtlas2D,
@@ -32,9 +107,14 @@ pub struct FontAtlas {
     font_size: f32,
     _padding: u32,
     max_tile_size: Size,
+    metrics: FontMetrics,
 }
 
 impl FontAtlas {
+    pub fn get_metrics(&self) -> FontMetrics {
+        self.metrics
+    }
+
     // for debugging
     pub fn get_tile_dimensions(&self) -> Size {
         self.max_tile_size
@@ -55,24 +135,143 @@ impl FontAtlas {
         Ok(())
     }
 }
+const MEASURE_CACHE_CAPACITY: usize = 256;
+
+/// Least-recently-used cache of measured string widths, per font and then per text.
+///
+/// Nesting the maps lets lookups borrow `&str` keys, so a hit doesn't allocate.
+#[derive(Default)]
+struct MeasureCache {
+    entries: HashMap<String, HashMap<String, (f32, u64)>>,
+    len: usize,
+    clock: u64,
+    hits: u64,
+}
+
+impl MeasureCache {
+    fn get(&mut self, font_key: &str, text: &str) -> Option<f32> {
+        self.clock += 1;
+        let clock = self.clock;
+        let entry = self.entries.get_mut(font_key)?.get_mut(text)?;
+        entry.1 = clock;
+        self.hits += 1;
+        Some(entry.0)
+    }
+
+    fn insert(&mut self, font_key: &str, text: &str, width: f32) {
+        self.clock += 1;
+        if self.len >= MEASURE_CACHE_CAPACITY {
+            let oldest = self
+                .entries
+                .iter()
+                .flat_map(|(font, texts)| {
+                    texts.iter().map(move |(text, entry)| (font, text, entry))
+                })
+                .min_by_key(|(_, _, (_, used))| *used)
+                .map(|(font, text, _)| (font.clone(), text.clone()));
+            if let Some((font, text)) = oldest {
+                self.remove(&font, &text);
+            }
+        }
+        let previous = self
+            .entries
+            .entry(font_key.to_string())
+            .or_default()
+            .insert(text.to_string(), (width, self.clock));
+        if previous.is_none() {
+            self.len += 1;
+        }
+    }
+
+    fn remove(&mut self, font_key: &str, text: &str) {
+        let Some(texts) = self.entries.get_mut(font_key) else {
+            return;
+        };
+        if texts.remove(text).is_some() {
+            self.len -= 1;
+        }
+        if texts.is_empty() {
+            self.entries.remove(font_key);
+        }
+    }
+
+    fn invalidate_font(&mut self, font_key: &str) {
+        if let Some(texts) = self.entries.remove(font_key) {
+            self.len -= texts.len();
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct TextRenderer {
     font_atlases: HashMap<String, FontAtlas>,
+    font_fallbacks: HashMap<String, Vec<String>>,
+    measure_cache: RefCell<MeasureCache>,
 }
 
 impl TextRenderer {
     pub fn new() -> Self {
         Self {
             font_atlases: HashMap::new(),
+            font_fallbacks: HashMap::new(),
+            measure_cache: RefCell::new(MeasureCache::default()),
+        }
+    }
+
+    /// Appends `fallback_key` to the chain searched when `primary_key` lacks a glyph.
+    pub fn register_font_fallback(&mut self, primary_key: &str, fallback_key: &str) {
+        let chain = self
+            .font_fallbacks
+            .entry(primary_key.to_string())
+            .or_default();
+        if primary_key != fallback_key && !chain.iter().any(|key| key == fallback_key) {
+            chain.push(fallback_key.to_string());
+            self.measure_cache.borrow_mut().invalidate_font(primary_key);
         }
     }
 
+    pub fn font_atlas_id(&self, font_key: &str) -> Option<Uuid> {
+        self.font_atlases
+            .get(font_key)
+            .map(|font_atlas| font_atlas.atlas.get_id())
+    }
+
+    pub fn font_metrics(&self, font_key: &str) -> Option<FontMetrics> {
+        self.font_atlases.get(font_key).map(FontAtlas::get_metrics)
+    }
+
+    /// Finds the atlas that provides `c`, trying the primary font first and then its fallbacks in order.
+    fn glyph_source(&self, font_key: &str, c: char) -> Option<(&FontAtlas, &CharacterInfo)> {
+        let fallbacks = self.font_fallbacks.get(font_key).into_iter().flatten();
+        std::iter::once(font_key)
+            .chain(fallbacks.map(String::as_str))
+            .filter_map(|key| self.font_atlases.get(key))
+            .find_map(|atlas| atlas.get_char_info(c).map(|info| (atlas, info)))
+    }
+
     pub fn calculate_text_layout(
         &self,
         text: &str,
         font_key: &str,
         position: Position,
         scale_factor: f32,
+    ) -> Vec<CharacterRenderInfo> {
+        self.calculate_text_layout_with_line_height(
+            text,
+            font_key,
+            position,
+            scale_factor,
+            LineHeight::default(),
+        )
+    }
+
+    pub fn calculate_text_layout_with_line_height(
+        &self,
+        text: &str,
+        font_key: &str,
+        position: Position,
+        scale_factor: f32,
+        line_height: LineHeight,
     ) -> Vec<CharacterRenderInfo> {
         let mut chars_to_render = Vec::new();
         let font_atlas = match self.font_atlases.get(font_key) {
@@ -83,10 +282,13 @@ impl TextRenderer {
         // Calculate the initial baseline by offsetting from the top by the font ascender
         let initial_baseline = position.y + (font_atlas.font_size * 0.35); // Approximate ascender height
         let mut baseline_y = initial_baseline;
+        let line_advance = line_height
+            .absolute
+            .unwrap_or(font_atlas.font_size * 0.8 * line_height.multiplier);
 
-        for c in text.chars() {
+        for (char_index, c) in text.chars().enumerate() {
             if c == '\n' {
-                baseline_y += font_atlas.font_size * 0.8;
+                baseline_y += line_advance;
                 pen_x = position.x;
                 continue;
             }
@@ -98,16 +300,18 @@ impl TextRenderer {
                 continue;
             }
 
-            if let Some(char_info) = font_atlas.get_char_info(c) {
+            if let Some((source_atlas, char_info)) = self.glyph_source(font_key, c) {
                 let char_pos = Position {
                     x: pen_x + char_info.bearing.0 / scale_factor,
                     y: baseline_y - char_info.bearing.1 / scale_factor,
                 };
 
                 chars_to_render.push(CharacterRenderInfo {
-                    atlas_id: font_atlas.atlas.get_id(),
+                    atlas_id: source_atlas.atlas.get_id(),
                     tile_index: char_info.tile_index,
                     position: char_pos,
+                    char_index,
+                    character: c,
                 });
 
                 pen_x += char_info.advance_width / scale_factor;
@@ -115,6 +319,7 @@ impl TextRenderer {
         }
         chars_to_render
     }
+    #[allow(clippy::too_many_arguments)]
     pub fn store_font_atlas(
         &mut self,
         font_key: &str,
@@ -123,6 +328,7 @@ impl TextRenderer {
         font_size: f32,
         _padding: u32,
         max_tile_size: Size,
+        metrics: FontMetrics,
     ) {
         let font_atlas = FontAtlas {
             atlas,
@@ -130,8 +336,21 @@ impl TextRenderer {
             font_size,
             _padding,
             max_tile_size,
+            metrics,
         };
         self.font_atlases.insert(font_key.to_string(), font_atlas);
+        self.invalidate_measurements(font_key);
+    }
+
+    /// Drops cached widths measured with `font_key`, including through fonts that fall back to it.
+    fn invalidate_measurements(&self, font_key: &str) {
+        let mut cache = self.measure_cache.borrow_mut();
+        cache.invalidate_font(font_key);
+        for (primary_key, chain) in &self.font_fallbacks {
+            if chain.iter().any(|key| key == font_key) {
+                cache.invalidate_font(primary_key);
+            }
+        }
     }
 
     pub fn calculate_atlas_size(
@@ -174,15 +393,32 @@ impl TextRenderer {
             max_height,
         )
     }
-    pub fn measure_text(&self, text: &str, font_key: &str) -> f32 {
-        if let Some(font_atlas) = self.font_atlases.get(font_key) {
-            text.chars()
-                .filter_map(|c| font_atlas.char_map.get(&c))
-                .map(|info| info.advance_width)
-                .sum()
-        } else {
-            0.0
+    /// Logical width of `text` in `font_key`, for glyphs rasterized at `scale_factor` (the DPI
+    /// scale, as in `calculate_text_layout`).
+    ///
+    /// Repeated strings are served from a small LRU cache.
+    pub fn measure_text(&self, text: &str, font_key: &str, scale_factor: f32) -> f32 {
+        if !self.font_atlases.contains_key(font_key) {
+            return 0.0;
+        }
+        // cached in atlas pixels, so a DPI change only invalidates through the font reload
+        if let Some(width) = self.measure_cache.borrow_mut().get(font_key, text) {
+            return width / scale_factor;
         }
+        let width: f32 = text
+            .chars()
+            .filter_map(|c| self.glyph_source(font_key, c))
+            .map(|(_, info)| info.advance_width)
+            .sum();
+        self.measure_cache
+            .borrow_mut()
+            .insert(font_key, text, width);
+        width / scale_factor
+    }
+
+    /// Number of `measure_text` calls answered from the cache.
+    pub fn measure_cache_hits(&self) -> u64 {
+        self.measure_cache.borrow().hits
     }
     pub fn render_glyphs_to_atlas(
         font: &Font,
@@ -250,3 +486,135 @@ impl TextRenderer {
         Some((texture_data, char_map))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PlutoniumEngine;
+
+    const ROBOTO: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/media/roboto.ttf");
+
+    #[test]
+    fn measure_cache_counts_hits_and_forgets_invalidated_fonts() {
+        let mut cache = MeasureCache::default();
+        assert_eq!(cache.get("roboto", "Score: 10"), None);
+        cache.insert("roboto", "Score: 10", 80.0);
+        cache.insert("mono", "Score: 10", 90.0);
+
+        assert_eq!(cache.get("roboto", "Score: 10"), Some(80.0));
+        assert_eq!(cache.get("mono", "Score: 10"), Some(90.0));
+        assert_eq!(cache.hits, 2);
+
+        cache.invalidate_font("roboto");
+        assert_eq!(cache.get("roboto", "Score: 10"), None);
+        assert_eq!(cache.get("mono", "Score: 10"), Some(90.0));
+        assert_eq!(cache.len, 1);
+    }
+
+    #[test]
+    fn measure_cache_evicts_the_least_recently_used_entry() {
+        let mut cache = MeasureCache::default();
+        for i in 0..MEASURE_CACHE_CAPACITY {
+            cache.insert("roboto", &i.to_string(), i as f32);
+        }
+        // touch the oldest entry so the second oldest is evicted instead
+        assert_eq!(cache.get("roboto", "0"), Some(0.0));
+        cache.insert("roboto", "new", 1.0);
+
+        assert_eq!(cache.len, MEASURE_CACHE_CAPACITY);
+        assert_eq!(cache.get("roboto", "0"), Some(0.0));
+        assert_eq!(cache.get("roboto", "1"), None);
+        assert_eq!(cache.get("roboto", "new"), Some(1.0));
+    }
+
+    #[test]
+    fn reloading_a_fallback_invalidates_fonts_that_use_it() {
+        let mut renderer = TextRenderer::new();
+        renderer.register_font_fallback("ui", "emoji");
+        {
+            let mut cache = renderer.measure_cache.borrow_mut();
+            cache.insert("ui", "hi", 10.0);
+            cache.insert("emoji", "hi", 12.0);
+            cache.insert("mono", "hi", 14.0);
+        }
+
+        renderer.invalidate_measurements("emoji");
+        let mut cache = renderer.measure_cache.borrow_mut();
+        assert_eq!(cache.get("ui", "hi"), None);
+        assert_eq!(cache.get("emoji", "hi"), None);
+        assert_eq!(cache.get("mono", "hi"), Some(14.0));
+    }
+
+    #[test]
+    fn line_height_multiplier_scales_line_spacing() {
+        let Some(mut engine) = PlutoniumEngine::for_tests(64, 64) else {
+            return;
+        };
+        assert!(engine.load_font(ROBOTO, 20.0, "roboto").is_ok());
+        let line_spacing = |line_height| {
+            let layout = engine.text_renderer.calculate_text_layout_with_line_height(
+                "A\nA",
+                "roboto",
+                Position::default(),
+                1.0,
+                line_height,
+            );
+            layout[1].position.y - layout[0].position.y
+        };
+
+        let single = line_spacing(LineHeight::default());
+        let double = line_spacing(LineHeight {
+            multiplier: 2.0,
+            absolute: None,
+        });
+        assert!(single > 0.0);
+        assert!((double - 2.0 * single).abs() < 1e-3, "{single} vs {double}");
+    }
+
+    #[test]
+    fn missing_glyphs_come_from_the_fallback_font() {
+        let Some(mut engine) = PlutoniumEngine::for_tests(64, 64) else {
+            return;
+        };
+        assert!(engine.load_font(ROBOTO, 16.0, "ui").is_ok());
+        assert!(engine.load_font(ROBOTO, 16.0, "fallback").is_ok());
+        engine.register_font_fallback("ui", "fallback");
+        // drop 'A' from the primary font so only the fallback provides it
+        let renderer = &mut engine.text_renderer;
+        renderer
+            .font_atlases
+            .get_mut("ui")
+            .unwrap()
+            .char_map
+            .remove(&'A');
+
+        let atlas_for = |c| {
+            renderer
+                .glyph_source("ui", c)
+                .map(|(atlas, _)| atlas.atlas.get_id())
+        };
+        assert_eq!(atlas_for('A'), renderer.font_atlas_id("fallback"));
+        assert_eq!(atlas_for('B'), renderer.font_atlas_id("ui"));
+        assert_ne!(
+            renderer.font_atlas_id("ui"),
+            renderer.font_atlas_id("fallback")
+        );
+    }
+
+    #[test]
+    fn font_metrics_report_the_font_ascent() {
+        let Some(mut engine) = PlutoniumEngine::for_tests(64, 64) else {
+            return;
+        };
+        assert!(engine.set_dpi_scale_factor(2.0).is_ok());
+        assert!(engine.load_font(ROBOTO, 20.0, "roboto").is_ok());
+        let font = Font::try_from_vec(std::fs::read(ROBOTO).unwrap()).unwrap();
+        let expected = font.v_metrics(Scale::uniform(20.0));
+
+        // rasterized at 40px, but reported back in logical pixels
+        let metrics = engine.font_metrics("roboto").unwrap();
+        assert!((metrics.ascent - expected.ascent).abs() < 1e-3);
+        assert!((metrics.descent - expected.descent).abs() < 1e-3);
+        assert!(metrics.ascent > 0.0);
+    }
+}
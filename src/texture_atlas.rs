@@ -1,8 +1,10 @@
+use crate::error::EngineError;
 use crate::text::CharacterInfo;
+use crate::texture_svg::{check_texture_size, read_svg_file, TextureSVG};
 use crate::utils::*;
-use resvg::usvg::{Options, Tree};
 use std::collections::HashMap;
-use std::{fs, num::NonZeroU64};
+use std::fs;
+use std::num::NonZeroU64;
 use uuid::Uuid;
 use wgpu::util::DeviceExt;
 
@@ -31,6 +33,33 @@ impl BufferDimensions {
     }
 }
 
+const CACHE_MAGIC: &[u8; 8] = b"PLATLAS\0";
+const CACHE_VERSION: u32 = 1;
+
+/// Handle to tiles uploaded once with `PlutoniumEngine::build_static_atlas_batch`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StaticBatch {
+    pub(crate) id: Uuid,
+}
+
+/// GPU side of a `StaticBatch`: one `SpriteInstance` per tile in a shared vertex buffer.
+pub(crate) struct StaticBatchData {
+    pub(crate) atlas_id: Uuid,
+    /// `(tile_index, physical position)` for each tile, unsnapped.
+    pub(crate) tiles: Vec<(usize, Position)>,
+    pub(crate) instance_buffer: wgpu::Buffer,
+    /// Viewport, camera and pixel snapping the instances were last written for; `None` before
+    /// the first draw.
+    pub(crate) uploaded_for: Option<(Size, Position, bool)>,
+    pub(crate) uploads: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TileError {
+    AtlasNotFound,
+    TileIndexOutOfRange { index: usize, count: usize },
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub struct TextureAtlas {
@@ -49,7 +78,17 @@ pub struct TextureAtlas {
     uv_uniform_buffer: wgpu::Buffer,
     uv_bind_groups: Vec<wgpu::BindGroup>,
     uv_bind_group: wgpu::BindGroup,
+    /// Each tile's UV rect, in the same order as `uv_bind_groups`, for instanced draws.
+    tile_uvs: Vec<UVTransform>,
     tile_size: Size,
+    filter_mode: wgpu::FilterMode,
+    address_mode: AddressMode,
+    tile_names: HashMap<String, usize>,
+    /// Viewport and camera the transform uniform was last written for; `None` when dirty.
+    uploaded_transform: Option<(Size, Position)>,
+    /// Exactly the pixels last uploaded, kept so the atlas can be rebuilt without its source.
+    #[cfg(feature = "retain-cpu-textures")]
+    retained_rgba: Vec<u8>,
 }
 
 impl TextureAtlas {
@@ -63,9 +102,20 @@ impl TextureAtlas {
         camera_position: Position,
     ) {
         self.dimensions.set_pos(position);
+        self.mark_transform_dirty();
         self.update_transform_uniform(device, queue, viewport_size, camera_position);
     }
 
+    /// Forces the next `update_transform_uniform` to rewrite the GPU transform.
+    pub fn mark_transform_dirty(&mut self) {
+        self.uploaded_transform = None;
+    }
+
+    /// Whether the transform uniform is stale for the given viewport and camera.
+    pub fn is_transform_dirty(&self, viewport_size: Size, camera_position: Position) -> bool {
+        self.uploaded_transform != Some((viewport_size, camera_position))
+    }
+
     fn calculate_required_tiles(char_positions: &HashMap<char, CharacterInfo>) -> usize {
         let mut max_index = 0;
         for info in char_positions.values() {
@@ -86,6 +136,7 @@ impl TextureAtlas {
         queue: &wgpu::Queue,
         transform_bind_group_layout: &wgpu::BindGroupLayout,
         char_positions: &HashMap<char, CharacterInfo>,
+        filter_mode: wgpu::FilterMode,
     ) -> Option<Self> {
         // Create texture view for rendering
         let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
@@ -98,6 +149,8 @@ impl TextureAtlas {
                 [0.0, 0.0, 1.0, 0.0],
                 [0.0, 0.0, 0.0, 1.0],
             ],
+            opacity: 1.0,
+            _padding: [0.0; 3],
         };
 
         // Set up vertex and index buffers
@@ -145,7 +198,7 @@ impl TextureAtlas {
         // Set up memory alignment for UV buffer
         let alignment = 256; // WebGPU buffer alignment requirement
         let element_size = std::mem::size_of::<UVTransform>();
-        let aligned_element_size = (element_size + alignment - 1) / alignment * alignment;
+        let aligned_element_size = element_size.div_ceil(alignment) * alignment;
         let buffer_size = num_tiles * aligned_element_size;
 
         // Create single UV uniform buffer for all transforms
@@ -164,6 +217,7 @@ impl TextureAtlas {
         // Set up texture dimensions
         let dimensions = Rectangle::new(position.x, position.y, size.width, size.height);
         let mut uv_bind_groups = Vec::with_capacity(num_tiles);
+        let mut tile_uvs = Vec::with_capacity(num_tiles);
 
         // Create bind groups for each tile
         for tile_index in 0..num_tiles {
@@ -199,6 +253,7 @@ impl TextureAtlas {
 
                 // Debug output
                 uv_bind_groups.push(uv_bind_group);
+                tile_uvs.push(uv_transform);
             }
         }
 
@@ -232,7 +287,14 @@ impl TextureAtlas {
             uv_uniform_buffer,
             uv_bind_groups,
             uv_bind_group: default_uv_bind_group,
+            tile_uvs,
             tile_size,
+            filter_mode,
+            address_mode: AddressMode::Clamp,
+            tile_names: HashMap::new(),
+            uploaded_transform: None,
+            #[cfg(feature = "retain-cpu-textures")]
+            retained_rgba: Vec::new(),
         })
     }
 
@@ -247,12 +309,155 @@ impl TextureAtlas {
         screen_pos: Position,
         scale_factor: f32,
         tile_size: Size,
-    ) -> Option<Self> {
-        let (texture, pixel_size) = Self::svg_to_texture(file_path, device, queue, scale_factor)?;
+        filter_mode: wgpu::FilterMode,
+    ) -> Result<Self, EngineError> {
+        let (texture, pixel_size, pixmap) =
+            Self::svg_to_texture(file_path, device, queue, scale_factor)?;
+        let mut atlas = Self::from_texture(
+            texture_key,
+            device,
+            queue,
+            texture,
+            pixel_size,
+            texture_bind_group_layout,
+            transform_bind_group_layout,
+            screen_pos,
+            tile_size,
+            filter_mode,
+        );
+        atlas.retain_rgba(pixmap.data());
+        Ok(atlas)
+    }
 
+    /// Loads an atlas written by `save_cache`, skipping SVG rasterization entirely.
+    ///
+    /// Fails with `InvalidCache` if the file was saved at a different scale factor or tile size,
+    /// since its pixels wouldn't match what `new` would produce.
+    #[allow(clippy::too_many_arguments)]
+    pub fn load_cache(
+        cache_path: &str,
+        texture_key: Uuid,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        texture_bind_group_layout: &wgpu::BindGroupLayout,
+        transform_bind_group_layout: &wgpu::BindGroupLayout,
+        screen_pos: Position,
+        scale_factor: f32,
+        tile_size: Size,
+        filter_mode: wgpu::FilterMode,
+    ) -> Result<Self, EngineError> {
+        let bytes = fs::read(cache_path).map_err(|source| EngineError::Io {
+            path: cache_path.to_string(),
+            source,
+        })?;
+        let mut rest = bytes.as_slice();
+
+        if take(&mut rest, CACHE_MAGIC.len())? != CACHE_MAGIC {
+            return Err(EngineError::InvalidCache("not an atlas cache".to_string()));
+        }
+        let version = take_u32(&mut rest)?;
+        if version != CACHE_VERSION {
+            return Err(EngineError::InvalidCache(format!(
+                "version {} (expected {})",
+                version, CACHE_VERSION
+            )));
+        }
+        let cached_scale_factor = f32::from_bits(take_u32(&mut rest)?);
+        let cached_tile_size = Size::new(
+            f32::from_bits(take_u32(&mut rest)?),
+            f32::from_bits(take_u32(&mut rest)?),
+        );
+        if cached_scale_factor != scale_factor || cached_tile_size != tile_size {
+            return Err(EngineError::InvalidCache(
+                "saved with a different scale factor or tile size".to_string(),
+            ));
+        }
+        let width = take_u32(&mut rest)?;
+        let height = take_u32(&mut rest)?;
+
+        let mut tile_names = HashMap::new();
+        for _ in 0..take_u32(&mut rest)? {
+            let name_len = take_u32(&mut rest)? as usize;
+            let name = String::from_utf8(take(&mut rest, name_len)?.to_vec())
+                .map_err(|_| EngineError::InvalidCache("tile name is not UTF-8".to_string()))?;
+            tile_names.insert(name, take_u32(&mut rest)? as usize);
+        }
+
+        let rgba = take(&mut rest, width as usize * height as usize * 4)?;
+        check_texture_size(device, width, height)?;
+        let texture = TextureSVG::rgba_to_texture(device, queue, width, height, rgba);
+        let mut atlas = Self::from_texture(
+            texture_key,
+            device,
+            queue,
+            texture,
+            Size::new(width as f32, height as f32),
+            texture_bind_group_layout,
+            transform_bind_group_layout,
+            screen_pos,
+            tile_size,
+            filter_mode,
+        );
+        atlas.tile_names = tile_names;
+        atlas.retain_rgba(rgba);
+        Ok(atlas)
+    }
+
+    /// Writes the atlas pixels, tile size and tile names so `load_cache` can rebuild it later.
+    ///
+    /// `scale_factor` should be the one the atlas was rasterized at; it is checked on load.
+    pub fn save_cache(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        cache_path: &str,
+        scale_factor: f32,
+    ) -> Result<(), EngineError> {
+        let rgba = self.read_rgba(device, queue)?;
+
+        let mut bytes = Vec::with_capacity(rgba.len() + 64);
+        bytes.extend_from_slice(CACHE_MAGIC);
+        for value in [
+            CACHE_VERSION,
+            scale_factor.to_bits(),
+            self.tile_size.width.to_bits(),
+            self.tile_size.height.to_bits(),
+            self.dimensions.width as u32,
+            self.dimensions.height as u32,
+            self.tile_names.len() as u32,
+        ] {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        for (name, tile_index) in &self.tile_names {
+            bytes.extend_from_slice(&(name.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(name.as_bytes());
+            bytes.extend_from_slice(&(*tile_index as u32).to_le_bytes());
+        }
+        bytes.extend_from_slice(&rgba);
+
+        fs::write(cache_path, bytes).map_err(|source| EngineError::Io {
+            path: cache_path.to_string(),
+            source,
+        })
+    }
+
+    /// Builds the atlas around an already uploaded texture of `pixel_size` pixels.
+    #[allow(clippy::too_many_arguments)]
+    fn from_texture(
+        texture_key: Uuid,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        texture: wgpu::Texture,
+        pixel_size: Size,
+        texture_bind_group_layout: &wgpu::BindGroupLayout,
+        transform_bind_group_layout: &wgpu::BindGroupLayout,
+        screen_pos: Position,
+        tile_size: Size,
+        filter_mode: wgpu::FilterMode,
+    ) -> Self {
         let view: wgpu::TextureView = texture.create_view(&wgpu::TextureViewDescriptor::default());
 
-        let sampler = Self::create_sampler(device);
+        let sampler = Self::create_sampler(device, filter_mode, AddressMode::Clamp);
         let bind_group: wgpu::BindGroup =
             Self::create_bind_group(device, &view, &sampler, texture_bind_group_layout);
 
@@ -264,6 +469,8 @@ impl TextureAtlas {
                 [0.0, 0.0, 1.0, 0.0],
                 [0.0, 0.0, 0.0, 1.0],
             ],
+            opacity: 1.0,
+            _padding: [0.0; 3],
         };
 
         let transform_uniform_buffer = Self::create_uniform_buffer(device, &transform_uniform);
@@ -295,7 +502,7 @@ impl TextureAtlas {
 
         let alignment = 256;
         let element_size = std::mem::size_of::<UVTransform>();
-        let aligned_element_size = (element_size + alignment - 1) / alignment * alignment;
+        let aligned_element_size = element_size.div_ceil(alignment) * alignment;
         let buffer_size = num_tiles * aligned_element_size;
 
         let uv_uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
@@ -317,6 +524,21 @@ impl TextureAtlas {
             pixel_size.height,
         );
 
+        let tile_uvs: Vec<UVTransform> = (0..num_tiles)
+            .map(
+                |i| match Self::tile_uv_coordinates(i, tile_size, dimensions.size()) {
+                    Some(tile_rect) => UVTransform {
+                        uv_offset: [tile_rect.x, tile_rect.y],
+                        uv_scale: [tile_rect.width, tile_rect.height],
+                    },
+                    None => UVTransform {
+                        uv_offset: [0.0, 0.0],
+                        uv_scale: [1.0, 1.0],
+                    },
+                },
+            )
+            .collect();
+
         let uv_bind_groups = (0..num_tiles)
             .filter_map(|i| {
                 let offset = (i * aligned_element_size) as u64;
@@ -366,7 +588,7 @@ impl TextureAtlas {
             label: Some("Default UV Bind Group"),
         });
 
-        Some(Self {
+        Self {
             texture_key,
             texture,
             view,
@@ -382,15 +604,183 @@ impl TextureAtlas {
             uv_uniform_buffer,
             uv_bind_groups,
             uv_bind_group: default_uv_bind_group,
+            tile_uvs,
             tile_size,
-        })
+            filter_mode,
+            address_mode: AddressMode::Clamp,
+            tile_names: HashMap::new(),
+            uploaded_transform: None,
+            #[cfg(feature = "retain-cpu-textures")]
+            retained_rgba: Vec::new(),
+        }
+    }
+
+    #[cfg_attr(not(feature = "retain-cpu-textures"), allow(unused_variables))]
+    pub(crate) fn retain_rgba(&mut self, rgba: &[u8]) {
+        #[cfg(feature = "retain-cpu-textures")]
+        {
+            self.retained_rgba = rgba.to_vec();
+        }
+    }
+
+    /// The RGBA8 pixels currently on the GPU, as uploaded.
+    #[cfg(feature = "retain-cpu-textures")]
+    pub fn retained_rgba(&self) -> &[u8] {
+        &self.retained_rgba
+    }
+
+    /// Rebuilds the texture and every buffer and bind group on `device` from the retained
+    /// pixels and tile UVs, e.g. on a new device after the old one was lost.
+    #[cfg(feature = "retain-cpu-textures")]
+    pub fn reupload(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        texture_bind_group_layout: &wgpu::BindGroupLayout,
+        transform_bind_group_layout: &wgpu::BindGroupLayout,
+    ) {
+        let texture =
+            crate::texture_svg::recreate_texture(device, queue, &self.texture, &self.retained_rgba);
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let sampler = Self::create_sampler(device, self.filter_mode, self.address_mode);
+        self.bind_group =
+            Self::create_bind_group(device, &view, &sampler, texture_bind_group_layout);
+        self.texture = texture;
+        self.view = view;
+
+        let (_, vertex_buffer, index_buffer) = Self::initialize_buffers(device);
+        self.vertex_buffer = vertex_buffer;
+        self.index_buffer = index_buffer;
+        // keeps any adjusted texture coordinates
+        self.update_vertex_buffer(device);
+        self.transform_uniform_buffer =
+            Self::create_uniform_buffer(device, &self.transform_uniform);
+        self.transform_bind_group = Self::create_bind_group_for_transform(
+            device,
+            &self.transform_uniform_buffer,
+            transform_bind_group_layout,
+        );
+
+        let uv_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                entries: &[wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: wgpu::BufferSize::new(
+                            std::mem::size_of::<UVTransform>() as u64
+                        ),
+                    },
+                    count: None,
+                }],
+                label: Some("UV Bind Group Layout"),
+            });
+        // one aligned slot per tile, laid out as the constructors leave it
+        let alignment = 256;
+        let element_size = std::mem::size_of::<UVTransform>();
+        let aligned_element_size = element_size.div_ceil(alignment) * alignment;
+        let mut contents = vec![0u8; aligned_element_size * self.tile_uvs.len().max(1)];
+        for (slot, uv_transform) in contents
+            .chunks_mut(aligned_element_size)
+            .zip(&self.tile_uvs)
+        {
+            slot[..element_size].copy_from_slice(bytemuck::bytes_of(uv_transform));
+        }
+        self.uv_uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("UV Uniform Buffer"),
+            contents: &contents,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+        let uv_bind_group_at = |offset: u64| {
+            device.create_bind_group(&wgpu::BindGroupDescriptor {
+                layout: &uv_bind_group_layout,
+                entries: &[wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                        buffer: &self.uv_uniform_buffer,
+                        offset,
+                        size: NonZeroU64::new(element_size as u64),
+                    }),
+                }],
+                label: Some("UV Bind Group"),
+            })
+        };
+        self.uv_bind_groups = (0..self.tile_uvs.len())
+            .map(|i| uv_bind_group_at((i * aligned_element_size) as u64))
+            .collect();
+        self.uv_bind_group = uv_bind_group_at(0);
+        self.mark_transform_dirty();
+    }
+
+    /// Filtering used when sampling tiles: `Nearest` keeps pixel art crisp, `Linear` smooths.
+    pub fn filter_mode(&self) -> wgpu::FilterMode {
+        self.filter_mode
+    }
+
+    /// Flat tile index for `(row, col)` in the atlas grid, or `None` outside it.
+    pub fn tile_index_from_rc(&self, row: usize, col: usize) -> Option<usize> {
+        let (columns, _) = self.grid_size();
+        let index = row.checked_mul(columns)?.checked_add(col)?;
+        (col < columns && index < self.tile_count()).then_some(index)
+    }
+
+    /// Lets the tile at `tile_index` be looked up as `name`, replacing any previous tile of that name.
+    pub fn name_tile(&mut self, name: &str, tile_index: usize) {
+        self.tile_names.insert(name.to_string(), tile_index);
+    }
+
+    pub fn tile_index_by_name(&self, name: &str) -> Option<usize> {
+        self.tile_names.get(name).copied()
+    }
+
+    /// Whole tiles that fit across and down the atlas, as `(columns, rows)`.
+    pub fn grid_size(&self) -> (usize, usize) {
+        if self.tile_size.width <= 0.0 || self.tile_size.height <= 0.0 {
+            return (0, 0);
+        }
+        (
+            (self.dimensions.width / self.tile_size.width).floor() as usize,
+            (self.dimensions.height / self.tile_size.height).floor() as usize,
+        )
+    }
+
+    /// Number of drawable tiles; valid `tile_index` values are `0..tile_count()`.
+    pub fn tile_count(&self) -> usize {
+        self.uv_bind_groups.len()
+    }
+
+    /// Rebuilds the sampler so UVs outside 0..=1 follow `address_mode`.
+    ///
+    /// Tiles sample a sub-rectangle, so this only changes what a tile's edge texels blend with.
+    pub fn set_address_mode(
+        &mut self,
+        device: &wgpu::Device,
+        texture_bind_group_layout: &wgpu::BindGroupLayout,
+        address_mode: AddressMode,
+    ) {
+        self.address_mode = address_mode;
+        let sampler = Self::create_sampler(device, self.filter_mode, address_mode);
+        self.bind_group =
+            Self::create_bind_group(device, &self.view, &sampler, texture_bind_group_layout);
+    }
+
+    pub fn address_mode(&self) -> AddressMode {
+        self.address_mode
     }
 
     /// Creates a sampler for texture filtering.
-    fn create_sampler(device: &wgpu::Device) -> wgpu::Sampler {
+    fn create_sampler(
+        device: &wgpu::Device,
+        filter_mode: wgpu::FilterMode,
+        address_mode: AddressMode,
+    ) -> wgpu::Sampler {
         device.create_sampler(&wgpu::SamplerDescriptor {
-            mag_filter: wgpu::FilterMode::Linear,
-            min_filter: wgpu::FilterMode::Linear,
+            address_mode_u: address_mode.to_wgpu(),
+            address_mode_v: address_mode.to_wgpu(),
+            mag_filter: filter_mode,
+            min_filter: filter_mode,
             mipmap_filter: wgpu::FilterMode::Nearest,
             ..Default::default()
         })
@@ -532,6 +922,9 @@ impl TextureAtlas {
         viewport_size: Size,
         camera_position: Position,
     ) {
+        if !self.is_transform_dirty(viewport_size, camera_position) {
+            return;
+        }
         let viewport_width = viewport_size.width;
         let viewport_height = viewport_size.height;
 
@@ -561,6 +954,7 @@ impl TextureAtlas {
             0,
             bytemuck::cast_slice(&[self.transform_uniform]),
         );
+        self.uploaded_transform = Some((viewport_size, camera_position));
     }
 
     pub fn render<'a>(
@@ -607,12 +1001,41 @@ impl TextureAtlas {
         rpass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint16);
         rpass.draw_indexed(0..self.num_indices, 0, 0..1);
     }
+    /// UV rect `render_tile` samples for `tile_index`; out-of-range indices fall back the same way.
+    pub fn tile_uv(&self, tile_index: usize) -> UVTransform {
+        self.tile_uvs
+            .get(tile_index)
+            .or(self.tile_uvs.first())
+            .copied()
+            .unwrap_or(UVTransform {
+                uv_offset: [0.0, 0.0],
+                uv_scale: [1.0, 1.0],
+            })
+    }
+
+    /// Draws `instance_count` tiles, each described by a `SpriteInstance` in `instance_buffer`.
+    pub fn render_instanced<'a>(
+        &'a self,
+        rpass: &mut wgpu::RenderPass<'a>,
+        instanced_pipeline: &'a wgpu::RenderPipeline,
+        instance_buffer: &'a wgpu::Buffer,
+        instance_count: u32,
+    ) {
+        rpass.set_pipeline(instanced_pipeline);
+        rpass.set_bind_group(0, &self.bind_group, &[]);
+        rpass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        rpass.set_vertex_buffer(1, instance_buffer.slice(..));
+        rpass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint16);
+        rpass.draw_indexed(0..self.num_indices, 0, 0..instance_count);
+    }
+
     /// gets the transform uniform based on the viewport size and adjusts for position.
     pub fn get_transform_uniform(
         &self,
         viewport_size: Size,
         pos: Position,
         camera_position: Position,
+        params: &DrawParams,
     ) -> TransformUniform {
         let tile_width = self.tile_size.width;
         let tile_height = self.tile_size.height;
@@ -623,16 +1046,19 @@ impl TextureAtlas {
         let ndc_dx = (2.0 * (pos.x - camera_position.x)) / viewport_size.width - 1.0;
         let ndc_dy = 1.0 - (2.0 * (pos.y - camera_position.y)) / viewport_size.height;
 
-        let ndc_x = ndc_dx + width_ndc;
-        let ndc_y = ndc_dy - height_ndc;
+        let ndc_center = params.anchored_center(
+            Position {
+                x: ndc_dx,
+                y: ndc_dy,
+            },
+            Size::new(width_ndc, height_ndc),
+            viewport_size,
+        );
 
         TransformUniform {
-            transform: [
-                [1.0, 0.0, 0.0, 0.0],
-                [0.0, 1.0, 0.0, 0.0],
-                [0.0, 0.0, 1.0, 0.0],
-                [ndc_x, ndc_y, 0.0, 1.0],
-            ],
+            transform: params.transform_matrix(ndc_center, viewport_size),
+            opacity: params.opacity,
+            _padding: [0.0; 3],
         }
     }
 
@@ -717,19 +1143,18 @@ impl TextureAtlas {
         device: &wgpu::Device,
         queue: &wgpu::Queue,
         scale_factor: f32,
-    ) -> Option<(wgpu::Texture, Size)> {
-        let svg_data = fs::read_to_string(file_path)
-            .unwrap_or_else(|_| panic!("file not found: {}", file_path));
-        let opt = Options::default();
-        let fontdb = resvg::usvg::fontdb::Database::new();
-        let rtree = Tree::from_str(&svg_data, &opt, &fontdb).ok()?;
+    ) -> Result<(wgpu::Texture, Size, tiny_skia::Pixmap), EngineError> {
+        let svg_data = read_svg_file(file_path)?;
+        let rtree = TextureSVG::parse_svg(&svg_data)?;
         let original_size = rtree.size();
         let scaled_size = Size {
             width: original_size.width() * scale_factor,
             height: original_size.height() * scale_factor,
         };
+        check_texture_size(device, scaled_size.width as u32, scaled_size.height as u32)?;
         let mut pixmap =
-            tiny_skia::Pixmap::new(scaled_size.width as u32, scaled_size.height as u32)?;
+            tiny_skia::Pixmap::new(scaled_size.width as u32, scaled_size.height as u32)
+                .expect("pixmap size was checked above");
         pixmap.fill(tiny_skia::Color::TRANSPARENT);
 
         let transform = tiny_skia::Transform::from_scale(scale_factor, scale_factor);
@@ -755,8 +1180,7 @@ impl TextureAtlas {
         let bytes_per_pixel = 4;
         let unpadded_bytes_per_row = pixmap.width() as usize * bytes_per_pixel;
         const COPY_BYTES_PER_ROW_ALIGNMENT: usize = 256;
-        let padded_bytes_per_row = (unpadded_bytes_per_row + COPY_BYTES_PER_ROW_ALIGNMENT - 1)
-            / COPY_BYTES_PER_ROW_ALIGNMENT
+        let padded_bytes_per_row = unpadded_bytes_per_row.div_ceil(COPY_BYTES_PER_ROW_ALIGNMENT)
             * COPY_BYTES_PER_ROW_ALIGNMENT;
 
         let total_size = padded_bytes_per_row * pixmap.height() as usize;
@@ -801,12 +1225,13 @@ impl TextureAtlas {
         );
 
         queue.submit(std::iter::once(encoder.finish()));
-        Some((
+        Ok((
             svg_texture,
             Size {
                 width: scaled_size.width,
                 height: scaled_size.height,
             },
+            pixmap,
         ))
     }
 
@@ -830,10 +1255,39 @@ impl TextureAtlas {
         device: &wgpu::Device,
         queue: &wgpu::Queue,
         path: &str,
-    ) -> Result<(), String> {
+    ) -> Result<(), EngineError> {
+        let rgba = self.read_rgba(device, queue)?;
+
+        // Create the image buffer and save to PNG
+        let image_buffer = image::ImageBuffer::<image::Rgba<u8>, _>::from_raw(
+            self.dimensions.size().width as u32,
+            self.dimensions.size().height as u32,
+            rgba,
+        )
+        .ok_or_else(|| EngineError::Readback("Failed to create image buffer".to_string()))?;
+
+        image_buffer.save(path).map_err(|e| EngineError::Io {
+            path: path.to_string(),
+            source: match e {
+                image::ImageError::IoError(source) => source,
+                other => std::io::Error::other(other),
+            },
+        })?;
+
+        Ok(())
+    }
+
+    /// Copies the atlas texture back from the GPU as tightly packed RGBA8 rows.
+    pub fn read_rgba(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+    ) -> Result<Vec<u8>, EngineError> {
         // First verify that the texture has the correct usage flags
         if !self.texture.usage().contains(wgpu::TextureUsages::COPY_SRC) {
-            return Err("Texture does not have COPY_SRC usage flag".to_string());
+            return Err(EngineError::Readback(
+                "Texture does not have COPY_SRC usage flag".to_string(),
+            ));
         }
 
         // Create a buffer to copy texture data into
@@ -844,7 +1298,9 @@ impl TextureAtlas {
 
         // Verify dimensions are non-zero
         if buffer_dimensions.width == 0 || buffer_dimensions.height == 0 {
-            return Err("Invalid texture dimensions".to_string());
+            return Err(EngineError::Readback(
+                "Invalid texture dimensions".to_string(),
+            ));
         }
 
         let output_buffer = device.create_buffer(&wgpu::BufferDescriptor {
@@ -895,8 +1351,8 @@ impl TextureAtlas {
 
         device.poll(wgpu::Maintain::Wait);
         rx.recv()
-            .map_err(|e| format!("Failed to receive mapping result: {}", e))?
-            .map_err(|e| e.to_string())?;
+            .map_err(|e| EngineError::Readback(format!("Failed to receive mapping result: {}", e)))?
+            .map_err(|e| EngineError::Readback(e.to_string()))?;
 
         // Get the mapped data
         let padded_data = buffer_slice.get_mapped_range();
@@ -913,18 +1369,133 @@ impl TextureAtlas {
         drop(padded_data);
         output_buffer.unmap();
 
-        // Create the image buffer and save to PNG
-        let image_buffer = image::ImageBuffer::<image::Rgba<u8>, _>::from_raw(
-            buffer_dimensions.width,
-            buffer_dimensions.height,
-            rgba,
-        )
-        .ok_or("Failed to create image buffer")?;
+        Ok(rgba)
+    }
+}
 
-        image_buffer
-            .save(path)
-            .map_err(|e| format!("Failed to save PNG: {}", e))?;
+/// Whether `cache_path` exists and was written after `source_path` last changed.
+pub fn cache_is_fresh(cache_path: &str, source_path: &str) -> bool {
+    let modified = |path: &str| fs::metadata(path).and_then(|metadata| metadata.modified());
+    match (modified(cache_path), modified(source_path)) {
+        (Ok(cache), Ok(source)) => cache >= source,
+        _ => false,
+    }
+}
 
-        Ok(())
+fn take<'b>(bytes: &mut &'b [u8], len: usize) -> Result<&'b [u8], EngineError> {
+    if bytes.len() < len {
+        return Err(EngineError::InvalidCache("file is truncated".to_string()));
+    }
+    let (head, tail) = bytes.split_at(len);
+    *bytes = tail;
+    Ok(head)
+}
+
+fn take_u32(bytes: &mut &[u8]) -> Result<u32, EngineError> {
+    let head = take(bytes, 4)?;
+    Ok(u32::from_le_bytes([head[0], head[1], head[2], head[3]]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TileError;
+    use crate::utils::{Position, Size};
+    use crate::PlutoniumEngine;
+    use uuid::Uuid;
+
+    /// `examples/media/square.svg` (100x100) scaled up and cut into a 2x2 grid of 256px tiles.
+    fn two_by_two_atlas(engine: &mut PlutoniumEngine) -> Uuid {
+        let svg = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/media/square.svg");
+        let tile_size = Size {
+            width: 256.0,
+            height: 256.0,
+        };
+        let (atlas_id, _) = engine.create_texture_atlas_with_filter(
+            svg,
+            Position::default(),
+            5.12,
+            tile_size,
+            wgpu::FilterMode::Nearest,
+        );
+        atlas_id
+    }
+
+    #[test]
+    fn atlases_report_their_filter_mode() {
+        let Some(mut engine) = PlutoniumEngine::for_tests(64, 64) else {
+            return;
+        };
+        let nearest = two_by_two_atlas(&mut engine);
+        assert_eq!(
+            engine.atlas_map[&nearest].filter_mode(),
+            wgpu::FilterMode::Nearest
+        );
+
+        let svg = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/media/square.svg");
+        let (linear, _) = engine.create_texture_atlas(
+            svg,
+            Position::default(),
+            1.0,
+            Size {
+                width: 50.0,
+                height: 50.0,
+            },
+        );
+        assert_eq!(
+            engine.atlas_map[&linear].filter_mode(),
+            wgpu::FilterMode::Linear
+        );
+    }
+
+    #[test]
+    fn tile_index_from_rc_is_row_major_within_the_grid() {
+        let Some(mut engine) = PlutoniumEngine::for_tests(64, 64) else {
+            return;
+        };
+        let atlas_id = two_by_two_atlas(&mut engine);
+
+        assert_eq!(engine.tile_index_from_rc(&atlas_id, 0, 0), Some(0));
+        assert_eq!(engine.tile_index_from_rc(&atlas_id, 0, 1), Some(1));
+        assert_eq!(engine.tile_index_from_rc(&atlas_id, 1, 0), Some(2));
+        assert_eq!(engine.tile_index_from_rc(&atlas_id, 1, 1), Some(3));
+        assert_eq!(engine.tile_index_from_rc(&atlas_id, 0, 2), None);
+        assert_eq!(engine.tile_index_from_rc(&atlas_id, 2, 0), None);
+        assert_eq!(engine.tile_index_from_rc(&atlas_id, usize::MAX, 0), None);
+        assert_eq!(engine.tile_index_from_rc(&atlas_id, 0, usize::MAX), None);
+        assert_eq!(engine.tile_index_from_rc(&Uuid::nil(), 0, 0), None);
+    }
+
+    #[test]
+    fn grid_size_and_tile_count_match_the_tiling() {
+        let Some(mut engine) = PlutoniumEngine::for_tests(64, 64) else {
+            return;
+        };
+        let atlas_id = two_by_two_atlas(&mut engine);
+
+        assert_eq!(engine.atlas_grid_size(&atlas_id), Some((2, 2)));
+        assert_eq!(engine.atlas_tile_count(&atlas_id), Some(4));
+        assert_eq!(engine.atlas_grid_size(&Uuid::nil()), None);
+        assert_eq!(engine.atlas_tile_count(&Uuid::nil()), None);
+    }
+
+    #[test]
+    fn try_queue_tile_reports_what_went_wrong() {
+        let Some(mut engine) = PlutoniumEngine::for_tests(64, 64) else {
+            return;
+        };
+        let atlas_id = two_by_two_atlas(&mut engine);
+
+        assert_eq!(
+            engine.try_queue_tile(&atlas_id, 3, Position::default()),
+            Ok(())
+        );
+        assert_eq!(
+            engine.try_queue_tile(&atlas_id, 4, Position::default()),
+            Err(TileError::TileIndexOutOfRange { index: 4, count: 4 })
+        );
+        assert_eq!(
+            engine.try_queue_tile(&Uuid::nil(), 0, Position::default()),
+            Err(TileError::AtlasNotFound)
+        );
     }
 }
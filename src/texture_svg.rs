@@ -1,3 +1,4 @@
+use crate::error::EngineError;
 use crate::utils::*;
 use resvg::usvg::{Options, Tree};
 use std::{fs, num::NonZeroU64};
@@ -23,6 +24,15 @@ pub struct TextureSVG {
     num_indices: u32,
     uv_uniform_buffer: wgpu::Buffer,
     uv_bind_group: wgpu::BindGroup,
+    address_mode: AddressMode,
+    /// Viewport and camera the transform uniform was last written for; `None` when dirty.
+    uploaded_transform: Option<(Size, Position)>,
+    /// Times the transform uniform was written to the GPU.
+    #[cfg(test)]
+    pub(crate) transform_writes: u32,
+    /// Exactly the pixels last uploaded, kept so the texture can be rebuilt without its source.
+    #[cfg(feature = "retain-cpu-textures")]
+    retained_rgba: Vec<u8>,
 }
 
 impl TextureSVG {
@@ -36,9 +46,20 @@ impl TextureSVG {
         camera_position: Position,
     ) {
         self.dimensions.set_pos(position);
+        self.mark_transform_dirty();
         self.update_transform_uniform(device, queue, viewport_size, camera_position);
     }
 
+    /// Forces the next `update_transform_uniform` to rewrite the GPU transform.
+    pub fn mark_transform_dirty(&mut self) {
+        self.uploaded_transform = None;
+    }
+
+    /// Whether the transform uniform is stale for the given viewport and camera.
+    pub fn is_transform_dirty(&self, viewport_size: Size, camera_position: Position) -> bool {
+        self.uploaded_transform != Some((viewport_size, camera_position))
+    }
+
     /// Updates the text content of the existing texture without recreating it.
     pub fn update_text(
         &mut self,
@@ -94,8 +115,7 @@ impl TextureSVG {
         let bytes_per_pixel = 4;
         let unpadded_bytes_per_row = pixmap.width() as usize * bytes_per_pixel;
         const COPY_BYTES_PER_ROW_ALIGNMENT: usize = 256;
-        let padded_bytes_per_row = ((unpadded_bytes_per_row + COPY_BYTES_PER_ROW_ALIGNMENT - 1)
-            / COPY_BYTES_PER_ROW_ALIGNMENT)
+        let padded_bytes_per_row = unpadded_bytes_per_row.div_ceil(COPY_BYTES_PER_ROW_ALIGNMENT)
             * COPY_BYTES_PER_ROW_ALIGNMENT;
 
         let total_size = padded_bytes_per_row * pixmap.height() as usize;
@@ -144,6 +164,7 @@ impl TextureSVG {
 
         // Optionally, update UV coordinates or other related data here
         // For example, if the actual rendered size is different, adjust accordingly
+        self.mark_transform_dirty();
         self.update_transform_uniform(device, queue, viewport_size, camera_position);
 
         Ok(())
@@ -160,12 +181,131 @@ impl TextureSVG {
         transform_bind_group_layout: &wgpu::BindGroupLayout,
         screen_pos: Position,
         scale_factor: f32,
-    ) -> Option<Self> {
-        let (texture, pixel_size) = Self::svg_to_texture(file_path, device, queue, scale_factor)?;
+    ) -> Result<Self, EngineError> {
+        let svg_data = read_svg_file(file_path)?;
+        Self::from_svg_data(
+            texture_key,
+            device,
+            queue,
+            &svg_data,
+            texture_bind_group_layout,
+            transform_bind_group_layout,
+            screen_pos,
+            scale_factor,
+        )
+    }
+
+    /// Creates a new `TextureSVG` from in-memory SVG markup.
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_svg_data(
+        texture_key: Uuid,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        svg_data: &str,
+        texture_bind_group_layout: &wgpu::BindGroupLayout,
+        transform_bind_group_layout: &wgpu::BindGroupLayout,
+        screen_pos: Position,
+        scale_factor: f32,
+    ) -> Result<Self, EngineError> {
+        let (texture, pixel_size, pixmap) =
+            Self::svg_to_texture(svg_data, device, queue, scale_factor)?;
+        let mut svg_texture = Self::from_texture(
+            texture_key,
+            device,
+            texture,
+            pixel_size,
+            texture_bind_group_layout,
+            transform_bind_group_layout,
+            screen_pos,
+        );
+        svg_texture.retain_rgba(pixmap.data());
+        Ok(svg_texture)
+    }
+
+    /// Creates a new `TextureSVG` rasterized to exactly `pixel_width` x `pixel_height`,
+    /// stretching the SVG if the aspect ratios differ.
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_svg_data_at_resolution(
+        texture_key: Uuid,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        svg_data: &str,
+        texture_bind_group_layout: &wgpu::BindGroupLayout,
+        transform_bind_group_layout: &wgpu::BindGroupLayout,
+        screen_pos: Position,
+        pixel_width: u32,
+        pixel_height: u32,
+    ) -> Result<Self, EngineError> {
+        let rtree = Self::parse_svg(svg_data)?;
+        let original_size = rtree.size();
+        check_texture_size(device, pixel_width, pixel_height)?;
+        let pixmap = Self::render_tree(
+            &rtree,
+            pixel_width,
+            pixel_height,
+            pixel_width as f32 / original_size.width(),
+            pixel_height as f32 / original_size.height(),
+        )
+        .expect("pixmap size was checked above");
+        let texture = Self::rgba_to_texture(
+            device,
+            queue,
+            pixmap.width(),
+            pixmap.height(),
+            pixmap.data(),
+        );
+        let mut svg_texture = Self::from_texture(
+            texture_key,
+            device,
+            texture,
+            Size::new(pixel_width as f32, pixel_height as f32),
+            texture_bind_group_layout,
+            transform_bind_group_layout,
+            screen_pos,
+        );
+        svg_texture.retain_rgba(pixmap.data());
+        Ok(svg_texture)
+    }
+
+    /// Creates a new `TextureSVG` from already rasterized RGBA pixels, premultiplied like the
+    /// pixels of SVG textures.
+    pub fn from_rgba(
+        texture_key: Uuid,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        image: &image::RgbaImage,
+        texture_bind_group_layout: &wgpu::BindGroupLayout,
+        transform_bind_group_layout: &wgpu::BindGroupLayout,
+        screen_pos: Position,
+    ) -> Self {
+        let texture =
+            Self::rgba_to_texture(device, queue, image.width(), image.height(), image.as_raw());
+        let mut rgba_texture = Self::from_texture(
+            texture_key,
+            device,
+            texture,
+            Size::new(image.width() as f32, image.height() as f32),
+            texture_bind_group_layout,
+            transform_bind_group_layout,
+            screen_pos,
+        );
+        rgba_texture.retain_rgba(image.as_raw());
+        rgba_texture
+    }
 
+    /// Wraps an already uploaded texture of `pixel_size` with its buffers and bind groups.
+    fn from_texture(
+        texture_key: Uuid,
+        device: &wgpu::Device,
+        texture: wgpu::Texture,
+        pixel_size: Size,
+        texture_bind_group_layout: &wgpu::BindGroupLayout,
+        transform_bind_group_layout: &wgpu::BindGroupLayout,
+        screen_pos: Position,
+    ) -> Self {
         let view: wgpu::TextureView = texture.create_view(&wgpu::TextureViewDescriptor::default());
 
-        let sampler = Self::create_sampler(device);
+        let sampler = Self::create_sampler(device, AddressMode::Clamp);
         let bind_group: wgpu::BindGroup =
             Self::create_bind_group(device, &view, &sampler, texture_bind_group_layout);
 
@@ -177,6 +317,8 @@ impl TextureSVG {
                 [0.0, 0.0, 1.0, 0.0],
                 [0.0, 0.0, 0.0, 1.0],
             ],
+            opacity: 1.0,
+            _padding: [0.0; 3],
         };
 
         let transform_uniform_buffer = Self::create_uniform_buffer(device, &transform_uniform);
@@ -186,53 +328,7 @@ impl TextureSVG {
             transform_bind_group_layout,
         );
 
-        let uv_bind_group_layout =
-            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-                entries: &[wgpu::BindGroupLayoutEntry {
-                    binding: 0,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Uniform,
-                        has_dynamic_offset: false,
-                        min_binding_size: wgpu::BufferSize::new(
-                            std::mem::size_of::<UVTransform>() as u64
-                        ),
-                    },
-                    count: None,
-                }],
-                label: Some("UV Bind Group Layout"),
-            });
-
-        let num_tiles = 1;
-        let alignment = 256;
-        let element_size = std::mem::size_of::<UVTransform>();
-        let aligned_element_size = (element_size + alignment - 1) / alignment * alignment;
-        let buffer_size = num_tiles * aligned_element_size;
-
-        let uv_uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("UV Uniform Buffer"),
-            contents: bytemuck::cast_slice(&vec![
-                UVTransform {
-                    uv_offset: [0.0, 0.0],
-                    uv_scale: [1.0, 1.0]
-                };
-                buffer_size / element_size
-            ]),
-            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
-        });
-
-        let default_uv_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            layout: &uv_bind_group_layout,
-            entries: &[wgpu::BindGroupEntry {
-                binding: 0,
-                resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
-                    buffer: &uv_uniform_buffer,
-                    offset: 0,
-                    size: NonZeroU64::new(std::mem::size_of::<UVTransform>() as u64),
-                }),
-            }],
-            label: Some("Default UV Bind Group"),
-        });
+        let (uv_uniform_buffer, default_uv_bind_group) = Self::create_default_uv(device);
         let dimensions = Rectangle::new(
             screen_pos.x,
             screen_pos.y,
@@ -240,7 +336,7 @@ impl TextureSVG {
             pixel_size.height,
         );
 
-        Some(Self {
+        Self {
             texture_key,
             texture,
             view,
@@ -256,12 +352,109 @@ impl TextureSVG {
             num_indices: 6,
             uv_uniform_buffer,
             uv_bind_group: default_uv_bind_group,
-        })
+            address_mode: AddressMode::Clamp,
+            uploaded_transform: None,
+            #[cfg(test)]
+            transform_writes: 0,
+            #[cfg(feature = "retain-cpu-textures")]
+            retained_rgba: Vec::new(),
+        }
+    }
+
+    #[cfg_attr(not(feature = "retain-cpu-textures"), allow(unused_variables))]
+    fn retain_rgba(&mut self, rgba: &[u8]) {
+        #[cfg(feature = "retain-cpu-textures")]
+        {
+            self.retained_rgba = rgba.to_vec();
+        }
+    }
+
+    /// The RGBA8 pixels currently on the GPU, as uploaded.
+    #[cfg(feature = "retain-cpu-textures")]
+    pub fn retained_rgba(&self) -> &[u8] {
+        &self.retained_rgba
+    }
+
+    /// Rebuilds the texture and every buffer and bind group on `device` from the retained
+    /// pixels, e.g. on a new device after the old one was lost.
+    #[cfg(feature = "retain-cpu-textures")]
+    pub fn reupload(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        texture_bind_group_layout: &wgpu::BindGroupLayout,
+        transform_bind_group_layout: &wgpu::BindGroupLayout,
+    ) {
+        let texture = recreate_texture(device, queue, &self.texture, &self.retained_rgba);
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let sampler = Self::create_sampler(device, self.address_mode);
+        self.bind_group =
+            Self::create_bind_group(device, &view, &sampler, texture_bind_group_layout);
+        self.texture = texture;
+        self.view = view;
+
+        let (_, vertex_buffer, index_buffer) = Self::initialize_buffers(device);
+        self.vertex_buffer = vertex_buffer;
+        self.index_buffer = index_buffer;
+        // keeps any adjusted texture coordinates
+        self.update_vertex_buffer(device);
+        self.transform_uniform_buffer =
+            Self::create_uniform_buffer(device, &self.transform_uniform);
+        self.transform_bind_group = Self::create_bind_group_for_transform(
+            device,
+            &self.transform_uniform_buffer,
+            transform_bind_group_layout,
+        );
+        (self.uv_uniform_buffer, self.uv_bind_group) = Self::create_default_uv(device);
+        self.mark_transform_dirty();
+    }
+
+    /// Re-rasterizes new SVG markup into this texture, keeping its position and transform buffers.
+    pub fn replace_svg_data(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        svg_data: &str,
+        texture_bind_group_layout: &wgpu::BindGroupLayout,
+        scale_factor: f32,
+    ) -> Result<(), EngineError> {
+        let (texture, pixel_size, pixmap) =
+            Self::svg_to_texture(svg_data, device, queue, scale_factor)?;
+        self.retain_rgba(pixmap.data());
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let sampler = Self::create_sampler(device, self.address_mode);
+        self.bind_group =
+            Self::create_bind_group(device, &view, &sampler, texture_bind_group_layout);
+        self.texture = texture;
+        self.view = view;
+        self.dimensions.width = pixel_size.width;
+        self.dimensions.height = pixel_size.height;
+        self.mark_transform_dirty();
+        Ok(())
+    }
+
+    /// Rebuilds the sampler so UVs outside 0..=1 follow `address_mode`.
+    pub fn set_address_mode(
+        &mut self,
+        device: &wgpu::Device,
+        texture_bind_group_layout: &wgpu::BindGroupLayout,
+        address_mode: AddressMode,
+    ) {
+        self.address_mode = address_mode;
+        let sampler = Self::create_sampler(device, address_mode);
+        self.bind_group =
+            Self::create_bind_group(device, &self.view, &sampler, texture_bind_group_layout);
+    }
+
+    pub fn address_mode(&self) -> AddressMode {
+        self.address_mode
     }
 
     /// Creates a sampler for texture filtering.
-    fn create_sampler(device: &wgpu::Device) -> wgpu::Sampler {
+    fn create_sampler(device: &wgpu::Device, address_mode: AddressMode) -> wgpu::Sampler {
         device.create_sampler(&wgpu::SamplerDescriptor {
+            address_mode_u: address_mode.to_wgpu(),
+            address_mode_v: address_mode.to_wgpu(),
             mag_filter: wgpu::FilterMode::Linear,
             min_filter: wgpu::FilterMode::Linear,
             mipmap_filter: wgpu::FilterMode::Nearest,
@@ -329,6 +522,58 @@ impl TextureSVG {
         (vertices, vertex_buffer, index_buffer)
     }
 
+    /// Creates the UV uniform buffer holding the full-image transform, and its bind group.
+    fn create_default_uv(device: &wgpu::Device) -> (wgpu::Buffer, wgpu::BindGroup) {
+        let uv_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                entries: &[wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: wgpu::BufferSize::new(
+                            std::mem::size_of::<UVTransform>() as u64
+                        ),
+                    },
+                    count: None,
+                }],
+                label: Some("UV Bind Group Layout"),
+            });
+
+        let num_tiles = 1;
+        let alignment = 256;
+        let element_size = std::mem::size_of::<UVTransform>();
+        let aligned_element_size = element_size.div_ceil(alignment) * alignment;
+        let buffer_size = num_tiles * aligned_element_size;
+
+        let uv_uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("UV Uniform Buffer"),
+            contents: bytemuck::cast_slice(&vec![
+                UVTransform {
+                    uv_offset: [0.0, 0.0],
+                    uv_scale: [1.0, 1.0]
+                };
+                buffer_size / element_size
+            ]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let default_uv_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &uv_bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                    buffer: &uv_uniform_buffer,
+                    offset: 0,
+                    size: NonZeroU64::new(std::mem::size_of::<UVTransform>() as u64),
+                }),
+            }],
+            label: Some("Default UV Bind Group"),
+        });
+        (uv_uniform_buffer, default_uv_bind_group)
+    }
+
     /// Creates a uniform buffer for the transform matrix.
     fn create_uniform_buffer(
         device: &wgpu::Device,
@@ -405,6 +650,9 @@ impl TextureSVG {
         viewport_size: Size,
         camera_position: Position,
     ) {
+        if !self.is_transform_dirty(viewport_size, camera_position) {
+            return;
+        }
         let viewport_width = viewport_size.width;
         let viewport_height = viewport_size.height;
 
@@ -434,6 +682,11 @@ impl TextureSVG {
             0,
             bytemuck::cast_slice(&[self.transform_uniform]),
         );
+        self.uploaded_transform = Some((viewport_size, camera_position));
+        #[cfg(test)]
+        {
+            self.transform_writes += 1;
+        }
     }
 
     pub fn render<'a>(
@@ -451,12 +704,46 @@ impl TextureSVG {
         rpass.draw_indexed(0..self.num_indices, 0, 0..1);
     }
 
+    /// Like `render`, but samples through `uv_bind_group` instead of the full texture.
+    pub fn render_with_uv<'a>(
+        &'a self,
+        rpass: &mut wgpu::RenderPass<'a>,
+        render_pipeline: &'a wgpu::RenderPipeline,
+        transform_bind_group: &'a wgpu::BindGroup,
+        uv_bind_group: &'a wgpu::BindGroup,
+    ) {
+        rpass.set_pipeline(render_pipeline);
+        rpass.set_bind_group(0, &self.bind_group, &[]);
+        rpass.set_bind_group(1, transform_bind_group, &[]);
+        rpass.set_bind_group(2, uv_bind_group, &[]);
+        rpass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        rpass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint16);
+        rpass.draw_indexed(0..self.num_indices, 0, 0..1);
+    }
+
+    /// Draws `instance_count` copies, each transformed by a `TransformUniform` in `instance_buffer`.
+    pub fn render_instanced<'a>(
+        &'a self,
+        rpass: &mut wgpu::RenderPass<'a>,
+        instanced_pipeline: &'a wgpu::RenderPipeline,
+        instance_buffer: &'a wgpu::Buffer,
+        instance_count: u32,
+    ) {
+        rpass.set_pipeline(instanced_pipeline);
+        rpass.set_bind_group(0, &self.bind_group, &[]);
+        rpass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        rpass.set_vertex_buffer(1, instance_buffer.slice(..));
+        rpass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint16);
+        rpass.draw_indexed(0..self.num_indices, 0, 0..instance_count);
+    }
+
     /// gets the transform uniform based on the viewport size and adjusts for position.
     pub fn get_transform_uniform(
         &self,
         viewport_size: Size,
         pos: Position,
         camera_position: Position,
+        params: &DrawParams,
     ) -> TransformUniform {
         let width = self.dimensions.width;
         let height = self.dimensions.height;
@@ -468,16 +755,19 @@ impl TextureSVG {
         let ndc_dx = (2.0 * (pos.x - camera_position.x)) / viewport_size.width - 1.0;
         let ndc_dy = 1.0 - (2.0 * (pos.y - camera_position.y)) / viewport_size.height;
 
-        let ndc_x = ndc_dx + width_ndc;
-        let ndc_y = ndc_dy - height_ndc;
+        let ndc_center = params.anchored_center(
+            Position {
+                x: ndc_dx,
+                y: ndc_dy,
+            },
+            Size::new(width_ndc, height_ndc),
+            viewport_size,
+        );
 
         TransformUniform {
-            transform: [
-                [1.0, 0.0, 0.0, 0.0],
-                [0.0, 1.0, 0.0, 0.0],
-                [0.0, 0.0, 1.0, 0.0],
-                [ndc_x, ndc_y, 0.0, 1.0],
-            ],
+            transform: params.transform_matrix(ndc_center, viewport_size),
+            opacity: params.opacity,
+            _padding: [0.0; 3],
         }
     }
 
@@ -513,59 +803,108 @@ impl TextureSVG {
         ];
     }
 
-    /// Converts an SVG file to a wgpu texture.
+    /// Converts SVG markup to a wgpu texture, also handing back the rasterized pixmap.
     fn svg_to_texture(
-        file_path: &str,
+        svg_data: &str,
         device: &wgpu::Device,
         queue: &wgpu::Queue,
         scale_factor: f32,
-    ) -> Option<(wgpu::Texture, Size)> {
-        let svg_data = fs::read_to_string(file_path).expect("file should exist");
-        let opt = Options::default();
-        let fontdb = resvg::usvg::fontdb::Database::new();
-        let rtree = Tree::from_str(&svg_data, &opt, &fontdb).ok()?;
+    ) -> Result<(wgpu::Texture, Size, Pixmap), EngineError> {
+        let rtree = Self::parse_svg(svg_data)?;
         let original_size = rtree.size();
         let scaled_size = Size {
             width: original_size.width() * scale_factor,
             height: original_size.height() * scale_factor,
         };
-        let mut pixmap =
-            tiny_skia::Pixmap::new(scaled_size.width as u32, scaled_size.height as u32)?;
+        check_texture_size(device, scaled_size.width as u32, scaled_size.height as u32)?;
+        let pixmap = Self::render_tree(
+            &rtree,
+            scaled_size.width as u32,
+            scaled_size.height as u32,
+            scale_factor,
+            scale_factor,
+        )
+        .expect("pixmap size was checked above");
+
+        let svg_texture = Self::rgba_to_texture(
+            device,
+            queue,
+            pixmap.width(),
+            pixmap.height(),
+            pixmap.data(),
+        );
+        Ok((
+            svg_texture,
+            Size {
+                width: scaled_size.width,
+                height: scaled_size.height,
+            },
+            pixmap,
+        ))
+    }
+
+    pub(crate) fn parse_svg(svg_data: &str) -> Result<Tree, EngineError> {
+        let opt = Options::default();
+        let fontdb = resvg::usvg::fontdb::Database::new();
+        Tree::from_str(svg_data, &opt, &fontdb)
+            .map_err(|err| EngineError::SvgParse(err.to_string()))
+    }
+
+    /// Renders a parsed SVG into a transparent `width` x `height` pixmap.
+    pub(crate) fn render_tree(
+        rtree: &Tree,
+        width: u32,
+        height: u32,
+        scale_x: f32,
+        scale_y: f32,
+    ) -> Option<Pixmap> {
+        let mut pixmap = tiny_skia::Pixmap::new(width, height)?;
         pixmap.fill(tiny_skia::Color::TRANSPARENT);
 
-        let transform = tiny_skia::Transform::from_scale(scale_factor, scale_factor);
-        resvg::render(&rtree, transform, &mut pixmap.as_mut());
+        let transform = tiny_skia::Transform::from_scale(scale_x, scale_y);
+        resvg::render(rtree, transform, &mut pixmap.as_mut());
+        Some(pixmap)
+    }
 
+    /// Uploads tightly packed RGBA8 pixels into a new sampled texture.
+    pub(crate) fn rgba_to_texture(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        width: u32,
+        height: u32,
+        rgba: &[u8],
+    ) -> wgpu::Texture {
         let svg_texture = device.create_texture(&wgpu::TextureDescriptor {
             label: Some("SVG Texture"),
             size: wgpu::Extent3d {
-                width: pixmap.width(),
-                height: pixmap.height(),
+                width,
+                height,
                 depth_or_array_layers: 1,
             },
             mip_level_count: 1,
             sample_count: 1,
             dimension: wgpu::TextureDimension::D2,
             format: wgpu::TextureFormat::Rgba8UnormSrgb,
-            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::COPY_DST
+                | wgpu::TextureUsages::COPY_SRC,
             view_formats: &[wgpu::TextureFormat::Rgba8UnormSrgb],
         });
 
         let bytes_per_pixel = 4;
-        let unpadded_bytes_per_row = pixmap.width() as usize * bytes_per_pixel;
+        let unpadded_bytes_per_row = width as usize * bytes_per_pixel;
         const COPY_BYTES_PER_ROW_ALIGNMENT: usize = 256;
-        let padded_bytes_per_row = (unpadded_bytes_per_row + COPY_BYTES_PER_ROW_ALIGNMENT - 1)
-            / COPY_BYTES_PER_ROW_ALIGNMENT
+        let padded_bytes_per_row = unpadded_bytes_per_row.div_ceil(COPY_BYTES_PER_ROW_ALIGNMENT)
             * COPY_BYTES_PER_ROW_ALIGNMENT;
 
-        let total_size = padded_bytes_per_row * pixmap.height() as usize;
+        let total_size = padded_bytes_per_row * height as usize;
         let mut padded_buffer = vec![0u8; total_size];
 
-        for y in 0..pixmap.height() as usize {
+        for y in 0..height as usize {
             let dst_start = y * padded_bytes_per_row;
             let src_start = y * unpadded_bytes_per_row;
             padded_buffer[dst_start..dst_start + unpadded_bytes_per_row]
-                .copy_from_slice(&pixmap.data()[src_start..src_start + unpadded_bytes_per_row]);
+                .copy_from_slice(&rgba[src_start..src_start + unpadded_bytes_per_row]);
         }
 
         let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
@@ -583,7 +922,7 @@ impl TextureSVG {
                 layout: wgpu::ImageDataLayout {
                     offset: 0,
                     bytes_per_row: Some(padded_bytes_per_row as u32),
-                    rows_per_image: Some(pixmap.height()),
+                    rows_per_image: Some(height),
                 },
             },
             wgpu::ImageCopyTexture {
@@ -593,20 +932,14 @@ impl TextureSVG {
                 aspect: wgpu::TextureAspect::All,
             },
             wgpu::Extent3d {
-                width: pixmap.width(),
-                height: pixmap.height(),
+                width,
+                height,
                 depth_or_array_layers: 1,
             },
         );
 
         queue.submit(std::iter::once(encoder.finish()));
-        Some((
-            svg_texture,
-            Size {
-                width: scaled_size.width,
-                height: scaled_size.height,
-            },
-        ))
+        svg_texture
     }
 
     /// Swaps the active texture buffer.
@@ -620,3 +953,167 @@ impl TextureSVG {
         self.dimensions.contains(*pos)
     }
 }
+
+/// Rasterizes an SVG file to exactly `pixel_width` x `pixel_height` premultiplied RGBA pixels,
+/// the same pixels `create_texture_svg` uploads.
+///
+/// Touches no GPU state, so it can run on worker threads; upload the result with
+/// `PlutoniumEngine::create_texture_from_rgba`.
+pub fn rasterize_svg(
+    file_path: &str,
+    pixel_width: u32,
+    pixel_height: u32,
+) -> Result<image::RgbaImage, EngineError> {
+    let svg_data = read_svg_file(file_path)?;
+    let rtree = TextureSVG::parse_svg(&svg_data)?;
+    let original_size = rtree.size();
+    let pixmap = TextureSVG::render_tree(
+        &rtree,
+        pixel_width,
+        pixel_height,
+        pixel_width as f32 / original_size.width(),
+        pixel_height as f32 / original_size.height(),
+    )
+    .ok_or(EngineError::RasterSize {
+        width: pixel_width,
+        height: pixel_height,
+    })?;
+
+    Ok(
+        image::RgbaImage::from_raw(pixel_width, pixel_height, pixmap.take())
+            .expect("pixmap holds width * height RGBA pixels"),
+    )
+}
+
+pub(crate) fn read_svg_file(file_path: &str) -> Result<String, EngineError> {
+    fs::read_to_string(file_path).map_err(|source| EngineError::Io {
+        path: file_path.to_string(),
+        source,
+    })
+}
+
+/// Rejects sizes that would make an empty pixmap or exceed the device's 2D texture limit.
+pub(crate) fn check_texture_size(
+    device: &wgpu::Device,
+    width: u32,
+    height: u32,
+) -> Result<(), EngineError> {
+    let max = device.limits().max_texture_dimension_2d;
+    if width == 0 || height == 0 || width > max || height > max {
+        Err(EngineError::TextureSize { width, height, max })
+    } else {
+        Ok(())
+    }
+}
+
+/// Creates a texture shaped like `previous` and fills it with tightly packed RGBA8 `rgba`.
+#[cfg(feature = "retain-cpu-textures")]
+pub(crate) fn recreate_texture(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    previous: &wgpu::Texture,
+    rgba: &[u8],
+) -> wgpu::Texture {
+    let size = previous.size();
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Reuploaded Texture"),
+        size,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: previous.format(),
+        usage: previous.usage(),
+        view_formats: &[],
+    });
+    queue.write_texture(
+        wgpu::ImageCopyTexture {
+            texture: &texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+            aspect: wgpu::TextureAspect::All,
+        },
+        rgba,
+        wgpu::ImageDataLayout {
+            offset: 0,
+            bytes_per_row: Some(4 * size.width),
+            rows_per_image: Some(size.height),
+        },
+        size,
+    );
+    texture
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SQUARE_SVG: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/media/square.svg");
+
+    #[test]
+    fn rasterize_svg_matches_uploaded_pixels() {
+        let image = rasterize_svg(SQUARE_SVG, 100, 100).unwrap();
+        assert_eq!(image.dimensions(), (100, 100));
+        assert_eq!(image.get_pixel(50, 50).0, [0, 0, 255, 255]);
+
+        let rtree = TextureSVG::parse_svg(&read_svg_file(SQUARE_SVG).unwrap()).unwrap();
+        let pixmap = TextureSVG::render_tree(&rtree, 100, 100, 1.0, 1.0).unwrap();
+        assert_eq!(image.as_raw().as_slice(), pixmap.data());
+    }
+
+    #[test]
+    fn rasterize_svg_reports_errors() {
+        assert!(matches!(
+            rasterize_svg("missing.svg", 10, 10),
+            Err(EngineError::Io { .. })
+        ));
+        assert!(matches!(
+            rasterize_svg(SQUARE_SVG, 0, 10),
+            Err(EngineError::RasterSize { width: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn moving_one_texture_rewrites_only_its_transform() {
+        let Some(mut engine) = crate::PlutoniumEngine::for_tests(64, 64) else {
+            return;
+        };
+        let keys: Vec<Uuid> = (0..3)
+            .map(|i| {
+                let position = Position {
+                    x: i as f32 * 10.0,
+                    y: 0.0,
+                };
+                engine.create_texture_svg(SQUARE_SVG, position, 0.1).0
+            })
+            .collect();
+        engine.update(None, &None);
+        let total_writes = |engine: &crate::PlutoniumEngine| -> u32 {
+            keys.iter()
+                .map(|key| engine.texture_map[key].transform_writes)
+                .sum()
+        };
+        let before = total_writes(&engine);
+
+        engine.set_texture_position(&keys[1], Position { x: 40.0, y: 40.0 });
+        engine.update(None, &None);
+        engine.update(None, &None);
+        assert_eq!(total_writes(&engine), before + 1);
+        assert_eq!(engine.texture_map[&keys[1]].transform_writes, 2);
+    }
+
+    #[test]
+    fn rasterize_svg_runs_on_worker_threads() {
+        let expected = rasterize_svg(SQUARE_SVG, 64, 64).unwrap();
+        let images: Vec<image::RgbaImage> = std::thread::scope(|scope| {
+            let workers: Vec<_> = (0..4)
+                .map(|_| scope.spawn(|| rasterize_svg(SQUARE_SVG, 64, 64).unwrap()))
+                .collect();
+            workers
+                .into_iter()
+                .map(|worker| worker.join().unwrap())
+                .collect()
+        });
+        assert_eq!(images.len(), 4);
+        assert!(images.iter().all(|image| *image == expected));
+    }
+}
@@ -0,0 +1,245 @@
+use crate::utils::{Position, Rectangle, Size};
+use crate::{PlutoniumEngine, StaticBatch};
+use uuid::Uuid;
+
+struct Chunk {
+    /// None while the chunk has no tiles.
+    batch: Option<StaticBatch>,
+    dirty: bool,
+}
+
+/// Grid of atlas tiles split into square chunks, drawn only where the camera can see.
+///
+/// Each chunk is uploaded once as a static batch and only rebuilt after one of its tiles changes,
+/// so the per-frame cost follows the visible area rather than the size of the map.
+pub struct Tilemap {
+    atlas_id: Uuid,
+    origin: Position,
+    tile_size: Size,
+    width: usize,
+    height: usize,
+    chunk_size: usize,
+    tiles: Vec<Option<usize>>,
+    chunks: Vec<Chunk>,
+}
+
+impl Tilemap {
+    /// `width` and `height` are in tiles; `chunk_size` is the side length of a chunk in tiles.
+    pub fn new(
+        atlas_id: Uuid,
+        origin: Position,
+        tile_size: Size,
+        width: usize,
+        height: usize,
+        chunk_size: usize,
+    ) -> Self {
+        let chunk_size = chunk_size.max(1);
+        let chunk_count = width.div_ceil(chunk_size) * height.div_ceil(chunk_size);
+        Self {
+            atlas_id,
+            origin,
+            tile_size,
+            width,
+            height,
+            chunk_size,
+            tiles: vec![None; width * height],
+            chunks: (0..chunk_count)
+                .map(|_| Chunk {
+                    batch: None,
+                    dirty: true,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn tile(&self, x: usize, y: usize) -> Option<usize> {
+        if x < self.width && y < self.height {
+            self.tiles[y * self.width + x]
+        } else {
+            None
+        }
+    }
+
+    /// Sets or clears the tile at `(x, y)`; out-of-bounds writes are ignored.
+    pub fn set_tile(&mut self, x: usize, y: usize, tile_index: Option<usize>) {
+        if x >= self.width || y >= self.height {
+            return;
+        }
+        let slot = &mut self.tiles[y * self.width + x];
+        if *slot != tile_index {
+            *slot = tile_index;
+            let chunk = self.chunk_index(x / self.chunk_size, y / self.chunk_size);
+            self.chunks[chunk].dirty = true;
+        }
+    }
+
+    /// Indices of the chunks overlapping `view` (in world units), row by row.
+    pub fn visible_chunks(&self, view: Rectangle) -> Vec<usize> {
+        let chunk_width = self.tile_size.width * self.chunk_size as f32;
+        let chunk_height = self.tile_size.height * self.chunk_size as f32;
+        let chunks_x = self.width.div_ceil(self.chunk_size);
+        let chunks_y = self.height.div_ceil(self.chunk_size);
+
+        let range = |start: f32, end: f32, extent: f32, count: usize| {
+            let first = (start / extent).floor().max(0.0) as usize;
+            let last = ((end / extent).ceil().max(0.0) as usize).min(count);
+            first..last
+        };
+        let columns = range(
+            view.x - self.origin.x,
+            view.x + view.width - self.origin.x,
+            chunk_width,
+            chunks_x,
+        );
+        let rows = range(
+            view.y - self.origin.y,
+            view.y + view.height - self.origin.y,
+            chunk_height,
+            chunks_y,
+        );
+
+        rows.flat_map(|row| columns.clone().map(move |column| (column, row)))
+            .map(|(column, row)| self.chunk_index(column, row))
+            .collect()
+    }
+
+    /// Draws the chunks visible to the engine's camera, rebuilding any whose tiles changed.
+    pub fn render(&mut self, engine: &mut PlutoniumEngine) {
+        for chunk in self.visible_chunks(engine.visible_world_rect()) {
+            if self.chunks[chunk].dirty {
+                self.rebuild_chunk(engine, chunk);
+            }
+            if let Some(batch) = &self.chunks[chunk].batch {
+                engine.draw_static_batch(batch);
+            }
+        }
+    }
+
+    /// Frees the chunks' GPU batches; the map draws nothing until its tiles are set again.
+    pub fn unload(&mut self, engine: &mut PlutoniumEngine) {
+        for chunk in &mut self.chunks {
+            if let Some(batch) = chunk.batch.take() {
+                engine.remove_static_batch(batch);
+            }
+            chunk.dirty = true;
+        }
+    }
+
+    fn chunk_index(&self, column: usize, row: usize) -> usize {
+        row * self.width.div_ceil(self.chunk_size) + column
+    }
+
+    fn rebuild_chunk(&mut self, engine: &mut PlutoniumEngine, chunk: usize) {
+        let chunks_x = self.width.div_ceil(self.chunk_size);
+        let x0 = (chunk % chunks_x) * self.chunk_size;
+        let y0 = (chunk / chunks_x) * self.chunk_size;
+
+        let mut tiles = Vec::new();
+        for y in y0..(y0 + self.chunk_size).min(self.height) {
+            for x in x0..(x0 + self.chunk_size).min(self.width) {
+                if let Some(tile_index) = self.tiles[y * self.width + x] {
+                    let position = Position {
+                        x: self.origin.x + x as f32 * self.tile_size.width,
+                        y: self.origin.y + y as f32 * self.tile_size.height,
+                    };
+                    tiles.push((tile_index, position));
+                }
+            }
+        }
+        if let Some(old) = self.chunks[chunk].batch.take() {
+            engine.remove_static_batch(old);
+        }
+        self.chunks[chunk] = Chunk {
+            batch: (!tiles.is_empty())
+                .then(|| engine.build_static_atlas_batch(&self.atlas_id, &tiles)),
+            dirty: false,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map(width: usize, height: usize) -> Tilemap {
+        Tilemap::new(
+            Uuid::nil(),
+            Position { x: 0.0, y: 0.0 },
+            Size {
+                width: 10.0,
+                height: 10.0,
+            },
+            width,
+            height,
+            4,
+        )
+    }
+
+    #[test]
+    fn visible_chunks_follow_the_view() {
+        // 3x3 chunks of 40x40 world units
+        let tilemap = map(12, 12);
+        assert_eq!(
+            tilemap.visible_chunks(Rectangle::new(0.0, 0.0, 30.0, 30.0)),
+            vec![0]
+        );
+        assert_eq!(
+            tilemap.visible_chunks(Rectangle::new(50.0, 0.0, 30.0, 30.0)),
+            vec![1]
+        );
+        assert_eq!(
+            tilemap.visible_chunks(Rectangle::new(30.0, 30.0, 20.0, 20.0)),
+            vec![0, 1, 3, 4]
+        );
+        assert!(tilemap
+            .visible_chunks(Rectangle::new(-100.0, -100.0, 50.0, 50.0))
+            .is_empty());
+        assert!(tilemap
+            .visible_chunks(Rectangle::new(200.0, 0.0, 50.0, 50.0))
+            .is_empty());
+    }
+
+    #[test]
+    fn unchanged_chunk_is_not_reuploaded() {
+        let Some(mut engine) = PlutoniumEngine::for_tests(64, 64) else {
+            return;
+        };
+        let svg = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/media/square.svg");
+        let (atlas_id, _) = engine.create_texture_atlas_with_filter(
+            svg,
+            Position::default(),
+            1.0,
+            Size {
+                width: 50.0,
+                height: 50.0,
+            },
+            wgpu::FilterMode::Nearest,
+        );
+        let mut tilemap = Tilemap::new(
+            atlas_id,
+            Position::default(),
+            Size {
+                width: 8.0,
+                height: 8.0,
+            },
+            4,
+            4,
+            4,
+        );
+        tilemap.set_tile(0, 0, Some(0));
+        tilemap.set_tile(3, 3, Some(1));
+
+        tilemap.render(&mut engine);
+        let batch = tilemap.chunks[0].batch.expect("chunk has tiles");
+        tilemap.render(&mut engine);
+        assert_eq!(tilemap.chunks[0].batch, Some(batch));
+        assert_eq!(engine.static_batch_uploads(&batch), Some(1));
+
+        tilemap.set_tile(1, 1, Some(2));
+        tilemap.render(&mut engine);
+        let rebuilt = tilemap.chunks[0].batch.expect("chunk has tiles");
+        assert_ne!(rebuilt, batch);
+        assert_eq!(engine.static_batch_uploads(&batch), None);
+        assert_eq!(engine.static_batch_uploads(&rebuilt), Some(1));
+    }
+}
@@ -0,0 +1,230 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Caps how often frames are produced by sleeping away the rest of each frame's budget.
+///
+/// Call `wait` once per frame after presenting. With vsync on, a target above the display's
+/// refresh rate simply never sleeps, so the limiter only matters when capping below it.
+#[derive(Debug)]
+pub struct FrameLimiter {
+    target_frame_time: Option<Duration>,
+    frame_start: Instant,
+}
+
+impl FrameLimiter {
+    pub fn new(target_fps: Option<u32>) -> Self {
+        Self {
+            target_frame_time: Self::frame_time_for(target_fps),
+            frame_start: Instant::now(),
+        }
+    }
+
+    pub fn set_target_fps(&mut self, target_fps: Option<u32>) {
+        self.target_frame_time = Self::frame_time_for(target_fps);
+    }
+
+    pub fn target_frame_time(&self) -> Option<Duration> {
+        self.target_frame_time
+    }
+
+    /// How long to sleep after a frame that took `elapsed`; zero when uncapped or running late.
+    pub fn sleep_duration(&self, elapsed: Duration) -> Duration {
+        self.target_frame_time
+            .map(|target| target.saturating_sub(elapsed))
+            .unwrap_or(Duration::ZERO)
+    }
+
+    /// Sleeps out the remainder of the current frame and starts timing the next one.
+    pub fn wait(&mut self) {
+        let remaining = self.sleep_duration(self.frame_start.elapsed());
+        if !remaining.is_zero() {
+            std::thread::sleep(remaining);
+        }
+        self.frame_start = Instant::now();
+    }
+
+    fn frame_time_for(target_fps: Option<u32>) -> Option<Duration> {
+        target_fps
+            .filter(|fps| *fps > 0)
+            .map(|fps| Duration::from_secs_f64(1.0 / fps as f64))
+    }
+}
+
+/// Tracks per-frame delta time with a smoothed value and FPS estimate for display.
+///
+/// The raw `delta_time` still spikes on hitches; `smoothed_delta_time` is an exponential moving
+/// average, and `min_delta_time`/`max_delta_time` cover the last `window` frames.
+#[derive(Debug)]
+pub struct FrameTimer {
+    last_tick: Option<Instant>,
+    delta_time: f32,
+    smoothed_delta_time: f32,
+    smoothing: f32,
+    window: usize,
+    recent: VecDeque<f32>,
+}
+
+impl Default for FrameTimer {
+    fn default() -> Self {
+        Self::new(0.1, 120)
+    }
+}
+
+impl FrameTimer {
+    /// `smoothing` is the EMA weight given to each new frame (0..=1); `window` is the min/max history length.
+    pub fn new(smoothing: f32, window: usize) -> Self {
+        Self {
+            last_tick: None,
+            delta_time: 0.0,
+            smoothed_delta_time: 0.0,
+            smoothing: smoothing.clamp(0.0, 1.0),
+            window: window.max(1),
+            recent: VecDeque::with_capacity(window.max(1)),
+        }
+    }
+
+    /// Measures the time since the previous tick and records it; the first tick reports zero.
+    pub fn tick(&mut self) -> f32 {
+        let now = Instant::now();
+        let delta_time = self
+            .last_tick
+            .map(|last| now.duration_since(last).as_secs_f32())
+            .unwrap_or(0.0);
+        self.last_tick = Some(now);
+        if delta_time > 0.0 {
+            self.record(delta_time);
+        }
+        delta_time
+    }
+
+    /// Feeds an externally measured frame time, e.g. from a fixed-step simulation.
+    pub fn record(&mut self, delta_time: f32) {
+        self.delta_time = delta_time;
+        self.smoothed_delta_time = if self.recent.is_empty() {
+            delta_time
+        } else {
+            self.smoothed_delta_time + (delta_time - self.smoothed_delta_time) * self.smoothing
+        };
+
+        if self.recent.len() == self.window {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(delta_time);
+    }
+
+    pub fn delta_time(&self) -> f32 {
+        self.delta_time
+    }
+
+    pub fn smoothed_delta_time(&self) -> f32 {
+        self.smoothed_delta_time
+    }
+
+    /// Frames per second derived from the smoothed delta time.
+    pub fn fps(&self) -> f32 {
+        if self.smoothed_delta_time > 0.0 {
+            1.0 / self.smoothed_delta_time
+        } else {
+            0.0
+        }
+    }
+
+    pub fn min_delta_time(&self) -> f32 {
+        self.recent.iter().copied().reduce(f32::min).unwrap_or(0.0)
+    }
+
+    pub fn max_delta_time(&self) -> f32 {
+        self.recent.iter().copied().reduce(f32::max).unwrap_or(0.0)
+    }
+}
+
+/// Snapshot of the most recent frame, handed to `PlutoniumEngine::set_stats_reporter` callbacks.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FrameStats {
+    /// Frames rendered since the engine was created, including this one.
+    pub frame: u64,
+    /// Draw calls issued by this frame.
+    pub draw_calls: usize,
+    pub textures: usize,
+    pub atlases: usize,
+    pub objects: usize,
+    /// Average CPU time per frame spent in `update` since the previous report.
+    pub cpu_update_ms: f32,
+    /// Average CPU time per frame spent building and submitting `render` since the previous report.
+    pub cpu_render_ms: f32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limiter_sleeps_the_rest_of_an_early_frame() {
+        let limiter = FrameLimiter::new(Some(50));
+        assert_eq!(
+            limiter.sleep_duration(Duration::from_millis(5)),
+            Duration::from_millis(15)
+        );
+    }
+
+    #[test]
+    fn limiter_never_sleeps_after_a_late_frame() {
+        let limiter = FrameLimiter::new(Some(50));
+        assert_eq!(
+            limiter.sleep_duration(Duration::from_millis(20)),
+            Duration::ZERO
+        );
+        assert_eq!(
+            limiter.sleep_duration(Duration::from_millis(35)),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn uncapped_limiter_never_sleeps() {
+        for target_fps in [None, Some(0)] {
+            let limiter = FrameLimiter::new(target_fps);
+            assert_eq!(limiter.target_frame_time(), None);
+            assert_eq!(
+                limiter.sleep_duration(Duration::from_millis(1)),
+                Duration::ZERO
+            );
+        }
+    }
+
+    #[test]
+    fn smoothing_is_stable_for_constant_frame_times() {
+        let mut timer = FrameTimer::new(0.1, 10);
+        for _ in 0..50 {
+            timer.record(0.016);
+        }
+        assert!((timer.smoothed_delta_time() - 0.016).abs() < 1e-6);
+        assert!((timer.fps() - 62.5).abs() < 1e-2);
+    }
+
+    #[test]
+    fn smoothing_eases_toward_a_step_change() {
+        let mut timer = FrameTimer::new(0.5, 10);
+        timer.record(0.010);
+        timer.record(0.030);
+        assert!((timer.smoothed_delta_time() - 0.020).abs() < 1e-6);
+        timer.record(0.030);
+        assert!((timer.smoothed_delta_time() - 0.025).abs() < 1e-6);
+        assert_eq!(timer.delta_time(), 0.030);
+
+        for _ in 0..30 {
+            timer.record(0.030);
+        }
+        assert!((timer.smoothed_delta_time() - 0.030).abs() < 1e-6);
+    }
+
+    #[test]
+    fn min_and_max_cover_only_the_window() {
+        let mut timer = FrameTimer::new(0.1, 3);
+        for delta_time in [0.050, 0.010, 0.020, 0.030] {
+            timer.record(delta_time);
+        }
+        assert_eq!(timer.min_delta_time(), 0.010);
+        assert_eq!(timer.max_delta_time(), 0.030);
+    }
+}
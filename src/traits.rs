@@ -5,6 +5,7 @@ use crate::PlutoniumEngine;
 use std::collections::HashMap;
 use uuid::Uuid;
 use winit::keyboard::Key;
+use winit::window::CursorIcon;
 pub struct UpdateContext<'a> {
     pub device: &'a wgpu::Device,
     pub queue: &'a wgpu::Queue,
@@ -18,10 +19,25 @@ pub trait PlutoObject {
     fn get_id(&self) -> Uuid;
     fn dimensions(&self) -> Rectangle;
     fn pos(&self) -> Position;
+    /// Cursor to show while the mouse is over this object; `None` leaves the choice to what's below.
+    fn cursor_icon(&self) -> Option<CursorIcon> {
+        None
+    }
+    /// Hidden objects stay registered but aren't updated, hit-tested, or drawn.
+    ///
+    /// Objects without their own flag are always visible here; the engine still hides them by id
+    /// through `PlutoniumEngine::set_visible`.
+    fn is_visible(&self) -> bool {
+        true
+    }
 
     // setters
     fn set_dimensions(&mut self, new_dimensions: Rectangle);
     fn set_pos(&mut self, new_pos: Position);
+    fn set_visible(&mut self, _visible: bool) {}
+    /// Called by `PlutoniumEngine::set_dpi_scale_factor` once fonts are re-rasterized, so sizes
+    /// measured at the old scale can be refreshed.
+    fn rescale(&mut self, _text_renderer: &TextRenderer, _dpi_scale_factor: f32) {}
 
     fn update(
         &mut self,
@@ -37,7 +53,9 @@ pub trait PlutoObject {
     }
 
     fn render(&self, engine: &mut PlutoniumEngine) {
-        engine.queue_texture(&self.texture_key(), Some(self.pos()));
+        if self.is_visible() {
+            engine.queue_texture(&self.texture_key(), Some(self.pos()));
+        }
     }
 
     fn delete(&self, engine: &mut PlutoniumEngine) {
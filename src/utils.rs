@@ -28,9 +28,120 @@ pub struct Vertex {
 #[derive(Debug, Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
 pub struct TransformUniform {
     pub transform: [[f32; 4]; 4], // 4x4 transformation matrix
+    pub opacity: f32,             // multiplies the sampled alpha in the fragment shader
+    pub _padding: [f32; 3],       // keeps the struct a multiple of 16 bytes for WGSL
 }
 
+/// One copy drawn by the instanced pipeline: a `TransformUniform`'s transform and opacity, the
+/// part of the texture to sample, and a tint.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct SpriteInstance {
+    pub transform: [[f32; 4]; 4],
+    pub opacity: f32,
+    pub uv_offset: [f32; 2],
+    pub uv_scale: [f32; 2],
+    /// RGBA multiplied into the sampled texel; white leaves the texture as drawn.
+    pub color: [f32; 4],
+}
+
+impl SpriteInstance {
+    pub fn new(transform_uniform: &TransformUniform, uv_transform: UVTransform) -> Self {
+        SpriteInstance {
+            transform: transform_uniform.transform,
+            opacity: transform_uniform.opacity,
+            uv_offset: uv_transform.uv_offset,
+            uv_scale: uv_transform.uv_scale,
+            color: [1.0; 4],
+        }
+    }
+}
+
+/// Per-draw parameters applied on top of a queued texture or tile's position.
 #[derive(Debug, Clone, Copy)]
+pub struct DrawParams {
+    /// Rotation around the quad's center in radians, clockwise on screen.
+    pub rotation: f32,
+    /// Horizontal stretch applied before rotation.
+    pub scale_x: f32,
+    /// Vertical stretch applied before rotation.
+    pub scale_y: f32,
+    /// Multiplies the texture's alpha, 0 (invisible) to 1 (as drawn).
+    pub opacity: f32,
+    /// Point of the quad, normalized 0..1 from its top-left, that lands on the draw position
+    /// and that rotation and scale pivot about.
+    ///
+    /// `None` keeps the original behavior: the position is the top-left of the unscaled quad
+    /// and transforms pivot about its center.
+    pub anchor: Option<Position>,
+}
+
+impl Default for DrawParams {
+    fn default() -> Self {
+        DrawParams {
+            rotation: 0.0,
+            scale_x: 1.0,
+            scale_y: 1.0,
+            opacity: 1.0,
+            anchor: None,
+        }
+    }
+}
+
+impl DrawParams {
+    /// Uniform scale convenience, sets both `scale_x` and `scale_y`.
+    pub fn with_scale(scale: f32) -> Self {
+        DrawParams {
+            scale_x: scale,
+            scale_y: scale,
+            ..Default::default()
+        }
+    }
+
+    /// NDC point to center the quad on so the anchor ends up at `ndc_position`.
+    ///
+    /// `half_extent` is half the unscaled quad's size in NDC, matching its vertices.
+    pub fn anchored_center(
+        &self,
+        ndc_position: Position,
+        half_extent: Size,
+        viewport_size: Size,
+    ) -> Position {
+        let Some(anchor) = self.anchor else {
+            return Position {
+                x: ndc_position.x + half_extent.width,
+                y: ndc_position.y - half_extent.height,
+            };
+        };
+        // the anchor relative to the quad's center in vertex space (y up), then through the
+        // same scale and rotation `transform_matrix` applies
+        let local_x = (2.0 * anchor.x - 1.0) * half_extent.width;
+        let local_y = (1.0 - 2.0 * anchor.y) * half_extent.height;
+        let [column_x, column_y, _, _] = self.transform_matrix(Position::default(), viewport_size);
+        Position {
+            x: ndc_position.x - (column_x[0] * local_x + column_y[0] * local_y),
+            y: ndc_position.y - (column_x[1] * local_x + column_y[1] * local_y),
+        }
+    }
+
+    /// Builds the transform for a quad centered at `ndc_center`.
+    ///
+    /// Quad vertices are already scaled into NDC, so scale and rotation are done in pixel space
+    /// (scaled by the viewport aspect) to keep non-square viewports from shearing the quad.
+    pub fn transform_matrix(&self, ndc_center: Position, viewport_size: Size) -> [[f32; 4]; 4] {
+        let (sin, cos) = self.rotation.sin_cos();
+        let aspect = viewport_size.width / viewport_size.height;
+
+        [
+            [cos * self.scale_x, -sin * aspect * self.scale_x, 0.0, 0.0],
+            [sin / aspect * self.scale_y, cos * self.scale_y, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [ndc_center.x, ndc_center.y, 0.0, 1.0],
+        ]
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Size {
     pub width: f32,
     pub height: f32,
@@ -204,6 +315,89 @@ impl Div<f32> for Rectangle {
         Rectangle::new(self.x, self.y, self.width / factor, self.height / factor)
     }
 }
+
+/// Stable hash of the rectangles' positions and sizes, in order, for pinning a layout in tests.
+///
+/// Uses FNV-1a over the raw float bits so the value is the same across runs and platforms,
+/// unlike the randomly seeded std hasher.
+pub fn layout_hash(rects: &[Rectangle]) -> u64 {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    rects
+        .iter()
+        .flat_map(|rect| [rect.x, rect.y, rect.width, rect.height])
+        .flat_map(|value| value.to_bits().to_le_bytes())
+        .fold(FNV_OFFSET_BASIS, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+        })
+}
+
+/// What sampling does with UVs outside 0..=1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AddressMode {
+    /// Repeat the edge texels.
+    #[default]
+    Clamp,
+    /// Wrap around, so UV 1.5 samples the same texel as 0.5.
+    Repeat,
+    /// Wrap around, flipping every other repetition.
+    MirrorRepeat,
+}
+
+impl AddressMode {
+    pub fn to_wgpu(self) -> wgpu::AddressMode {
+        match self {
+            AddressMode::Clamp => wgpu::AddressMode::ClampToEdge,
+            AddressMode::Repeat => wgpu::AddressMode::Repeat,
+            AddressMode::MirrorRepeat => wgpu::AddressMode::MirrorRepeat,
+        }
+    }
+}
+
+/// Alternate ways to draw the render queue for diagnosing batching and overdraw.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DebugMode {
+    #[default]
+    Normal,
+    /// Triangle edges only; needs `wgpu::Features::POLYGON_MODE_LINE` on the adapter.
+    Wireframe,
+    /// Each draw adds a constant gray over black, so heavily overdrawn areas are brightest.
+    Overdraw,
+}
+
+/// How a fixed virtual resolution is fit into the window.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LetterboxMode {
+    /// Scale uniformly to fit and fill the leftover bars with `bar_color` (RGBA, 0..=1).
+    Letterbox { bar_color: [f32; 4] },
+    /// Fill the whole window, ignoring the aspect ratio.
+    Stretch,
+}
+
+impl LetterboxMode {
+    /// Window-pixel rect the virtual resolution is drawn into, centered for `Letterbox`.
+    pub fn viewport_rect(&self, virtual_size: Size, window_size: Size) -> Rectangle {
+        match self {
+            LetterboxMode::Stretch => {
+                Rectangle::new(0.0, 0.0, window_size.width, window_size.height)
+            }
+            LetterboxMode::Letterbox { .. } => {
+                let scale = (window_size.width / virtual_size.width)
+                    .min(window_size.height / virtual_size.height);
+                let width = virtual_size.width * scale;
+                let height = virtual_size.height * scale;
+                Rectangle::new(
+                    (window_size.width - width) / 2.0,
+                    (window_size.height - height) / 2.0,
+                    width,
+                    height,
+                )
+            }
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 pub struct MouseInfo {
     pub is_rmb_clicked: bool,
@@ -211,3 +405,190 @@ pub struct MouseInfo {
     pub is_mmb_clicked: bool,
     pub mouse_pos: Position,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VIEWPORT: Size = Size {
+        width: 200.0,
+        height: 100.0,
+    };
+
+    /// Pixel-space bounding box of a `width` x `height` pixel quad drawn with `params` so its
+    /// (unanchored) top-left lands on `position`, the way textures and tiles are placed.
+    fn screen_rect(params: &DrawParams, position: Position, width: f32, height: f32) -> Rectangle {
+        let ndc_position = Position {
+            x: position.x / VIEWPORT.width * 2.0 - 1.0,
+            y: 1.0 - position.y / VIEWPORT.height * 2.0,
+        };
+        let half_extent = Size {
+            width: width / VIEWPORT.width,
+            height: height / VIEWPORT.height,
+        };
+        let center = params.anchored_center(ndc_position, half_extent, VIEWPORT);
+        let [column_x, column_y, _, translation] = params.transform_matrix(center, VIEWPORT);
+
+        let corners = [(-1.0, 1.0), (1.0, 1.0), (1.0, -1.0), (-1.0, -1.0)].map(|(sx, sy)| {
+            let (x, y) = (sx * half_extent.width, sy * half_extent.height);
+            let ndc_x = column_x[0] * x + column_y[0] * y + translation[0];
+            let ndc_y = column_x[1] * x + column_y[1] * y + translation[1];
+            (
+                (ndc_x + 1.0) / 2.0 * VIEWPORT.width,
+                (1.0 - ndc_y) / 2.0 * VIEWPORT.height,
+            )
+        });
+        let min_x = corners.iter().map(|c| c.0).fold(f32::MAX, f32::min);
+        let max_x = corners.iter().map(|c| c.0).fold(f32::MIN, f32::max);
+        let min_y = corners.iter().map(|c| c.1).fold(f32::MAX, f32::min);
+        let max_y = corners.iter().map(|c| c.1).fold(f32::MIN, f32::max);
+        Rectangle::new(min_x, min_y, max_x - min_x, max_y - min_y)
+    }
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() < 1e-3,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn default_params_place_the_top_left_at_the_position() {
+        let rect = screen_rect(
+            &DrawParams::default(),
+            Position { x: 30.0, y: 10.0 },
+            40.0,
+            20.0,
+        );
+        assert_close(rect.x, 30.0);
+        assert_close(rect.y, 10.0);
+        assert_close(rect.width, 40.0);
+        assert_close(rect.height, 20.0);
+    }
+
+    #[test]
+    fn quarter_turn_swaps_width_and_height_about_the_center() {
+        let params = DrawParams {
+            rotation: std::f32::consts::FRAC_PI_2,
+            ..Default::default()
+        };
+        let rect = screen_rect(&params, Position { x: 30.0, y: 10.0 }, 40.0, 20.0);
+        assert_close(rect.width, 20.0);
+        assert_close(rect.height, 40.0);
+        // still centered where the unrotated quad was
+        assert_close(rect.x + rect.width / 2.0, 50.0);
+        assert_close(rect.y + rect.height / 2.0, 20.0);
+    }
+
+    #[test]
+    fn non_uniform_scale_stretches_each_axis() {
+        let params = DrawParams {
+            scale_x: 2.0,
+            scale_y: 0.5,
+            ..Default::default()
+        };
+        let rect = screen_rect(&params, Position { x: 30.0, y: 10.0 }, 40.0, 20.0);
+        assert_close(rect.width, 80.0);
+        assert_close(rect.height, 10.0);
+        assert_close(rect.x + rect.width / 2.0, 50.0);
+        assert_close(rect.y + rect.height / 2.0, 20.0);
+    }
+
+    #[test]
+    fn with_scale_sets_both_axes() {
+        let params = DrawParams::with_scale(3.0);
+        assert_eq!((params.scale_x, params.scale_y), (3.0, 3.0));
+    }
+
+    #[test]
+    fn top_left_anchor_puts_the_corner_on_the_position() {
+        let params = DrawParams {
+            scale_x: 2.0,
+            anchor: Some(Position { x: 0.0, y: 0.0 }),
+            ..Default::default()
+        };
+        let rect = screen_rect(&params, Position { x: 30.0, y: 10.0 }, 40.0, 20.0);
+        assert_close(rect.x, 30.0);
+        assert_close(rect.y, 10.0);
+        assert_close(rect.width, 80.0);
+        assert_close(rect.height, 20.0);
+    }
+
+    #[test]
+    fn rotation_pivots_about_the_anchor() {
+        let params = DrawParams {
+            rotation: std::f32::consts::PI,
+            anchor: Some(Position { x: 0.5, y: 1.0 }),
+            ..Default::default()
+        };
+        // a half turn about the bottom-center flips the quad to hang below the pivot
+        let rect = screen_rect(&params, Position { x: 50.0, y: 50.0 }, 40.0, 20.0);
+        assert_close(rect.x, 30.0);
+        assert_close(rect.y, 50.0);
+        assert_close(rect.width, 40.0);
+        assert_close(rect.height, 20.0);
+    }
+
+    #[test]
+    fn letterbox_pillarboxes_16_9_into_4_3() {
+        let mode = LetterboxMode::Letterbox {
+            bar_color: [0.0, 0.0, 0.0, 1.0],
+        };
+        let rect = mode.viewport_rect(Size::new(1600.0, 900.0), Size::new(800.0, 600.0));
+        assert_close(rect.x, 0.0);
+        assert_close(rect.y, 75.0);
+        assert_close(rect.width, 800.0);
+        assert_close(rect.height, 450.0);
+    }
+
+    #[test]
+    fn letterbox_centers_a_narrow_resolution() {
+        let mode = LetterboxMode::Letterbox {
+            bar_color: [0.0, 0.0, 0.0, 1.0],
+        };
+        let rect = mode.viewport_rect(Size::new(100.0, 100.0), Size::new(200.0, 100.0));
+        assert_close(rect.x, 50.0);
+        assert_close(rect.y, 0.0);
+        assert_close(rect.width, 100.0);
+        assert_close(rect.height, 100.0);
+    }
+
+    #[test]
+    fn stretch_fills_the_window() {
+        let rect =
+            LetterboxMode::Stretch.viewport_rect(Size::new(1600.0, 900.0), Size::new(800.0, 600.0));
+        assert_close(rect.x, 0.0);
+        assert_close(rect.y, 0.0);
+        assert_close(rect.width, 800.0);
+        assert_close(rect.height, 600.0);
+    }
+
+    #[test]
+    fn layout_hash_is_stable_across_runs() {
+        assert_eq!(layout_hash(&[]), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(
+            layout_hash(&[Rectangle::new(0.0, 0.0, 10.0, 10.0)]),
+            0xe2f1_794d_4e6a_2275
+        );
+    }
+
+    #[test]
+    fn layout_hash_detects_moved_and_reordered_rects() {
+        let menu = [
+            Rectangle::new(10.0, 10.0, 100.0, 30.0),
+            Rectangle::new(10.0, 50.0, 100.0, 30.0),
+        ];
+        let rebuilt = [
+            Rectangle::new(10.0, 10.0, 100.0, 30.0),
+            Rectangle::new(10.0, 50.0, 100.0, 30.0),
+        ];
+        assert_eq!(layout_hash(&menu), layout_hash(&rebuilt));
+
+        let mut moved = menu;
+        moved[1].y += 1.0;
+        assert_ne!(layout_hash(&menu), layout_hash(&moved));
+
+        let reordered = [menu[1], menu[0]];
+        assert_ne!(layout_hash(&menu), layout_hash(&reordered));
+    }
+}